@@ -0,0 +1,65 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use ray_tracer_challenge::affine::Affine;
+use ray_tracer_challenge::material::Material;
+use ray_tracer_challenge::shape::Shape;
+use ray_tracer_challenge::shape::sphere::Sphere;
+use ray_tracer_challenge::transform::{self, Transformation};
+use ray_tracer_challenge::vector::{ORIGIN, Point, Vector};
+use std::f64::consts::FRAC_PI_3;
+use std::hint::black_box;
+
+const TRANSFORMATIONS: [Transformation; 4] = [
+    Transformation::Scale(1.0, 2.0, 0.5),
+    Transformation::RotateY(FRAC_PI_3),
+    Transformation::Shear(0.2, 0.0, 0.0, 0.1, 0.0, 0.0),
+    Transformation::Translate(1.0, -2.0, 3.0),
+];
+
+fn inverse(c: &mut Criterion) {
+    let matrix = transform::transform(&TRANSFORMATIONS);
+    let affine = Affine::from(&matrix);
+
+    c.bench_function("matrix inverse", |b| {
+        b.iter(|| black_box(&matrix).inverse())
+    });
+    c.bench_function("affine inverse", |b| {
+        b.iter(|| black_box(&affine).inverse())
+    });
+}
+
+fn normal_at(c: &mut Criterion) {
+    let transformation = transform::transform(&TRANSFORMATIONS);
+    let inverse_transformation = transformation.inverse().unwrap();
+    let sphere = Sphere::with_transformations(&TRANSFORMATIONS, Material::default());
+    let point = &transformation * &Point::new(0.0, 1.0, 0.0);
+
+    // The path shapes took before caching: transpose the full inverse on every call
+    c.bench_function("uncached normal", |b| {
+        b.iter(|| {
+            let point = black_box(&point);
+            let object_normal = &inverse_transformation * point - &ORIGIN;
+            let mut components = &inverse_transformation.transpose() * object_normal.components();
+            components[3] = 0.0;
+
+            Vector::from(components).normalize()
+        })
+    });
+    c.bench_function("cached normal", |b| {
+        b.iter(|| sphere.normal_at(black_box(&point), 0.0))
+    });
+}
+
+fn compose(c: &mut Criterion) {
+    let matrix = transform::transform(&TRANSFORMATIONS);
+    let affine = Affine::from(&matrix);
+
+    c.bench_function("matrix compose", |b| {
+        b.iter(|| black_box(&matrix) * black_box(&matrix))
+    });
+    c.bench_function("affine compose", |b| {
+        b.iter(|| black_box(&affine) * black_box(&affine))
+    });
+}
+
+criterion_group!(benches, inverse, normal_at, compose);
+criterion_main!(benches);
@@ -0,0 +1,202 @@
+use crate::matrix::{Matrix, SingularMatrix};
+use crate::ray::Ray;
+use crate::vector::{Point, Vector};
+use std::ops::Mul;
+
+/// An affine transformation: a linear map followed by a translation, stored as the top three
+/// rows of a 4×4 transformation matrix. Affine transformations can be applied and inverted with
+/// much less arithmetic than general 4×4 matrices.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Affine {
+    rows: [[f64; 4]; 3],
+}
+
+impl Affine {
+    pub fn new(rows: [[f64; 4]; 3]) -> Self {
+        Self { rows }
+    }
+
+    pub fn identity() -> Self {
+        Self::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+
+    /// Returns the linear part of this transformation, without its translation.
+    pub fn linear(&self) -> Matrix<3> {
+        Matrix::new(self.rows.map(|[a, b, c, _]| [a, b, c]))
+    }
+
+    /// Returns the inverse of this transformation: the inverse of the linear part, found from
+    /// cross products of its columns, followed by the opposite of the original translation
+    /// mapped through it.
+    pub fn inverse(&self) -> Result<Affine, SingularMatrix> {
+        let column =
+            |col: usize| Vector::new(self.rows[0][col], self.rows[1][col], self.rows[2][col]);
+        let (x_axis, y_axis, z_axis) = (column(0), column(1), column(2));
+
+        let determinant = x_axis.dot(&y_axis.cross(&z_axis));
+
+        if determinant == 0.0 {
+            return Err(SingularMatrix);
+        }
+
+        // The rows of the inverse are perpendicular to all but one of the original columns
+        let inverse_rows = [
+            y_axis.cross(&z_axis) / determinant,
+            z_axis.cross(&x_axis) / determinant,
+            x_axis.cross(&y_axis) / determinant,
+        ];
+
+        let translation = column(3);
+
+        Ok(Self::new(inverse_rows.map(|row| {
+            let [a, b, c, _] = row.components();
+
+            [a, b, c, -row.dot(&translation)]
+        })))
+    }
+}
+
+/// Takes the top three rows of a transformation matrix, whose bottom row should be (0, 0, 0, 1).
+impl From<&Matrix<4>> for Affine {
+    fn from(matrix: &Matrix<4>) -> Self {
+        debug_assert!(
+            matrix[(3, 0)] == 0.0
+                && matrix[(3, 1)] == 0.0
+                && matrix[(3, 2)] == 0.0
+                && matrix[(3, 3)] == 1.0
+        );
+
+        Self::new([0, 1, 2].map(|row| [0, 1, 2, 3].map(|col| matrix[(row, col)])))
+    }
+}
+
+impl From<&Affine> for Matrix<4> {
+    fn from(affine: &Affine) -> Self {
+        let [r0, r1, r2] = affine.rows;
+
+        Matrix::new([r0, r1, r2, [0.0, 0.0, 0.0, 1.0]])
+    }
+}
+
+/// Composes two transformations, so that `a * b` applies `b` and then `a`.
+impl Mul<&Affine> for &Affine {
+    type Output = Affine;
+
+    fn mul(self, rhs: &Affine) -> Self::Output {
+        let mut product = [[0.0; 4]; 3];
+
+        for (row, product) in self.rows.iter().zip(product.iter_mut()) {
+            for (n, element) in product.iter_mut().enumerate() {
+                *element = (row[0] * rhs.rows[0][n])
+                    + (row[1] * rhs.rows[1][n])
+                    + (row[2] * rhs.rows[2][n]);
+            }
+
+            product[3] += row[3];
+        }
+
+        Affine::new(product)
+    }
+}
+
+impl Mul<&Point> for &Affine {
+    type Output = Point;
+
+    fn mul(self, rhs: &Point) -> Self::Output {
+        let (x, y, z) = rhs.coordinates();
+        let [r0, r1, r2] = self
+            .rows
+            .map(|row| (row[0] * x) + (row[1] * y) + (row[2] * z) + row[3]);
+
+        Point::new(r0, r1, r2)
+    }
+}
+
+impl Mul<&Vector> for &Affine {
+    type Output = Vector;
+
+    fn mul(self, rhs: &Vector) -> Self::Output {
+        let [x, y, z, _] = rhs.components();
+        let [r0, r1, r2] = self
+            .rows
+            .map(|row| (row[0] * x) + (row[1] * y) + (row[2] * z));
+
+        Vector::new(r0, r1, r2)
+    }
+}
+
+impl Mul<&Ray> for &Affine {
+    type Output = Ray;
+
+    fn mul(self, rhs: &Ray) -> Self::Output {
+        Ray::new(self * rhs.origin(), self * rhs.direction()).with_time(rhs.time())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::affine::Affine;
+    use crate::matrix::Matrix;
+    use crate::ray::Ray;
+    use crate::transform::{Transformation, transform};
+    use crate::vector::{Point, Vector};
+
+    fn matrix() -> Matrix<4> {
+        transform(&[
+            Transformation::Scale(2.0, -3.0, 0.5),
+            Transformation::Shear(0.3, 0.0, 0.2, 0.0, 0.0, 0.1),
+            Transformation::Rotate {
+                axis: Vector::new(1.0, 2.0, 3.0),
+                angle: 0.8,
+            },
+            Transformation::Translate(1.0, -2.0, 3.0),
+        ])
+    }
+
+    #[test]
+    fn test_transform() {
+        let matrix = matrix();
+        let affine = Affine::from(&matrix);
+        let point = Point::new(0.5, -1.5, 2.0);
+        let vector = Vector::new(0.5, -1.5, 2.0);
+
+        (&matrix * &point).assert_approx_eq_epsilon(&(&affine * &point), 1e-12);
+        (&matrix * &vector).assert_approx_eq_epsilon(&(&affine * &vector), 1e-12);
+
+        let ray = &affine * &Ray::new(point, vector).with_time(0.5);
+
+        (&matrix * &point).assert_approx_eq_epsilon(ray.origin(), 1e-12);
+        assert_eq!(0.5, ray.time());
+
+        Matrix::from(&affine).assert_approx_eq(&matrix, 0.0);
+    }
+
+    #[test]
+    fn test_compose() {
+        let a = Affine::from(&matrix());
+        let b = Affine::from(&transform(&[
+            Transformation::RotateX(0.3),
+            Transformation::Translate(4.0, 5.0, 6.0),
+        ]));
+
+        Matrix::from(&(&a * &b)).assert_approx_eq(&(Matrix::from(&a) * Matrix::from(&b)), 1e-12);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let matrix = matrix();
+        let inverse = Affine::from(&matrix).inverse().unwrap();
+
+        Matrix::from(&inverse).assert_approx_eq(&matrix.inverse().unwrap(), 1e-12);
+        Matrix::from(&(&Affine::from(&matrix) * &inverse))
+            .assert_approx_eq(&Matrix::<4>::identity(), 1e-12);
+
+        let flattened = Affine::from(&transform(&[Transformation::Scale(1.0, 0.0, 1.0)]));
+
+        assert!(flattened.inverse().is_err());
+    }
+}
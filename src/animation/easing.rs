@@ -0,0 +1,91 @@
+/// The number of bisection steps taken to invert a Bézier easing curve, which is enough to find
+/// its parameter to within double precision.
+const BISECTION_STEPS: usize = 52;
+
+/// How a keyframed value moves from one keyframe to the next.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Easing {
+    /// Moves at a constant rate.
+    #[default]
+    Linear,
+    /// Follows a cubic Bézier curve from (0, 0) to (1, 1) with control points (x1, y1) and
+    /// (x2, y2), like CSS's `cubic-bezier()`. The x coordinates of the control points are clamped
+    /// to [0, 1] so that the curve never doubles back in time.
+    Bezier(f64, f64, f64, f64),
+}
+
+impl Easing {
+    /// Starts slowly and finishes at full speed.
+    pub const EASE_IN: Easing = Easing::Bezier(0.42, 0.0, 1.0, 1.0);
+
+    /// Starts at full speed and slows to a stop.
+    pub const EASE_OUT: Easing = Easing::Bezier(0.0, 0.0, 0.58, 1.0);
+
+    /// Starts slowly, speeds up and slows to a stop.
+    pub const EASE_IN_OUT: Easing = Easing::Bezier(0.42, 0.0, 0.58, 1.0);
+
+    /// Maps the fraction `t` of the time between two keyframes that has passed to the fraction of
+    /// the way between their values.
+    pub fn ease(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+
+        match *self {
+            Easing::Linear => t,
+            Easing::Bezier(x1, y1, x2, y2) => {
+                let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+
+                // The curve's x coordinate never decreases, so bisect to find where it reaches t
+                let (mut low, mut high) = (0.0, 1.0);
+
+                for _ in 0..BISECTION_STEPS {
+                    let middle = (low + high) / 2.0;
+
+                    if cubic_bezier(x1, x2, middle) < t {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+
+                cubic_bezier(y1, y2, (low + high) / 2.0)
+            }
+        }
+    }
+}
+
+/// Evaluates one coordinate of a cubic Bézier curve from 0 to 1 with the given inner control
+/// points at parameter `s`.
+fn cubic_bezier(p1: f64, p2: f64, s: f64) -> f64 {
+    let u = 1.0 - s;
+
+    (3.0 * u * u * s * p1) + (3.0 * u * s * s * p2) + (s * s * s)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::animation::easing::Easing;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+
+    #[test]
+    fn test_ease() {
+        assert_f64_near!(0.25, Easing::Linear.ease(0.25));
+        assert_f64_near!(1.0, Easing::Linear.ease(1.5));
+
+        for easing in [Easing::EASE_IN, Easing::EASE_OUT, Easing::EASE_IN_OUT] {
+            assert_float_absolute_eq!(0.0, easing.ease(0.0), 1e-12);
+            assert_float_absolute_eq!(1.0, easing.ease(1.0), 1e-12);
+        }
+
+        // Easing in lags behind linear motion, and easing out runs ahead of it
+        assert!(Easing::EASE_IN.ease(0.5) < 0.5);
+        assert!(Easing::EASE_OUT.ease(0.5) > 0.5);
+        assert_float_absolute_eq!(0.5, Easing::EASE_IN_OUT.ease(0.5), 1e-12);
+
+        // A curve with its control points on the diagonal is linear
+        let diagonal = Easing::Bezier(1.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0);
+
+        for t in [0.1, 0.3, 0.7, 0.9] {
+            assert_float_absolute_eq!(t, diagonal.ease(t), 1e-12);
+        }
+    }
+}
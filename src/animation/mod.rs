@@ -0,0 +1,103 @@
+pub mod easing;
+pub mod track;
+
+use crate::color::Color;
+use crate::transform::Transformation;
+use crate::vector::{Point, Vector};
+
+/// A value that can be blended with another of its kind, so that it can be keyframed.
+pub trait Interpolate: Clone {
+    /// Returns the value a fraction `t` of the way from this value to `other`.
+    fn interpolate(&self, other: &Self, t: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        self + ((other - self) * t)
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        *self + ((*other - *self) * t)
+    }
+}
+
+impl Interpolate for Point {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        *self + &((other - self) * t)
+    }
+}
+
+impl Interpolate for Vector {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        *self + &((*other - self) * t)
+    }
+}
+
+/// Lists of transformations blend transformation by transformation. Lists that don't have the
+/// same kinds of transformation in the same order can't be blended, and hold still until `t`
+/// reaches 1.
+impl Interpolate for Vec<Transformation> {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        let blended = (self.len() == other.len())
+            .then(|| {
+                self.iter()
+                    .zip(other)
+                    .map(|(a, b)| a.interpolate(b, t))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .flatten();
+
+        blended.unwrap_or_else(|| if t < 1.0 { self.clone() } else { other.clone() })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::animation::Interpolate;
+    use crate::color::Color;
+    use crate::transform::Transformation;
+    use crate::vector::{Point, Vector};
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_interpolate() {
+        assert_f64_near!(2.5, 2.0.interpolate(&4.0, 0.25));
+
+        Color::new(0.5, 0.25, 0.0).assert_approx_eq(
+            &Color::new(1.0, 0.0, 0.0).interpolate(&Color::new(0.0, 0.5, 0.0), 0.5),
+        );
+
+        Point::new(1.0, 2.0, 3.0).assert_approx_eq(
+            &Point::new(0.0, 0.0, 0.0).interpolate(&Point::new(2.0, 4.0, 6.0), 0.5),
+        );
+
+        Vector::new(0.0, 0.0, 1.5).assert_approx_eq(
+            &Vector::new(0.0, 0.0, 1.0).interpolate(&Vector::new(0.0, 0.0, 2.0), 0.5),
+        );
+
+        let start = vec![
+            Transformation::Scale(1.0, 1.0, 1.0),
+            Transformation::RotateZ(0.0),
+        ];
+        let end = vec![
+            Transformation::Scale(3.0, 3.0, 3.0),
+            Transformation::RotateZ(1.0),
+        ];
+
+        assert_eq!(
+            vec![
+                Transformation::Scale(2.0, 2.0, 2.0),
+                Transformation::RotateZ(0.5)
+            ],
+            start.interpolate(&end, 0.5)
+        );
+
+        // Mismatched lists jump from one to the other
+        let other = vec![Transformation::Translate(1.0, 0.0, 0.0)];
+
+        assert_eq!(start, start.interpolate(&other, 0.99));
+        assert_eq!(other, start.interpolate(&other, 1.0));
+    }
+}
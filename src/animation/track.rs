@@ -0,0 +1,110 @@
+use crate::animation::Interpolate;
+use crate::animation::easing::Easing;
+
+/// A value that changes over time, given by its value at keyframes and the easing used to move
+/// from each keyframe to the next.
+#[derive(Clone)]
+pub struct Track<T> {
+    keyframes: Vec<Keyframe<T>>,
+}
+
+#[derive(Clone)]
+struct Keyframe<T> {
+    time: f64,
+    value: T,
+    easing: Easing,
+}
+
+impl<T: Interpolate> Track<T> {
+    /// Creates a track that holds `value` from `time` on.
+    pub fn new(time: f64, value: T) -> Self {
+        Self {
+            keyframes: vec![Keyframe {
+                time,
+                value,
+                easing: Easing::Linear,
+            }],
+        }
+    }
+
+    /// Adds a keyframe that the track reaches from the keyframe before it with the given easing,
+    /// replacing any keyframe at the same time.
+    pub fn with_keyframe(mut self, time: f64, value: T, easing: Easing) -> Self {
+        let keyframe = Keyframe {
+            time,
+            value,
+            easing,
+        };
+
+        let index = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time < time);
+
+        match self.keyframes.get_mut(index) {
+            Some(existing) if existing.time == time => *existing = keyframe,
+            _ => self.keyframes.insert(index, keyframe),
+        }
+
+        self
+    }
+
+    /// Returns the time of the first keyframe.
+    pub fn start(&self) -> f64 {
+        self.keyframes[0].time
+    }
+
+    /// Returns the time of the last keyframe.
+    pub fn end(&self) -> f64 {
+        self.keyframes[self.keyframes.len() - 1].time
+    }
+
+    /// Returns the track's value at the given time. Before the first keyframe and after the
+    /// last, the value holds still.
+    pub fn value_at(&self, time: f64) -> T {
+        let next = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time);
+
+        if next == 0 || next == self.keyframes.len() {
+            return self.keyframes[next.saturating_sub(1)].value.clone();
+        }
+
+        let (start, end) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let t = (time - start.time) / (end.time - start.time);
+
+        start.value.interpolate(&end.value, end.easing.ease(t))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::animation::easing::Easing;
+    use crate::animation::track::Track;
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_value_at() {
+        let track = Track::new(1.0, 10.0)
+            .with_keyframe(3.0, 20.0, Easing::Linear)
+            .with_keyframe(2.0, 0.0, Easing::EASE_IN_OUT);
+
+        assert_f64_near!(1.0, track.start());
+        assert_f64_near!(3.0, track.end());
+
+        assert_f64_near!(10.0, track.value_at(0.0));
+        assert_f64_near!(5.0, track.value_at(1.5));
+        assert_f64_near!(0.0, track.value_at(2.0));
+        assert_f64_near!(10.0, track.value_at(2.5));
+        assert_f64_near!(20.0, track.value_at(4.0));
+
+        // Easing shapes the approach to a keyframe
+        let eased = Track::new(0.0, 0.0).with_keyframe(1.0, 1.0, Easing::EASE_IN);
+
+        assert!(eased.value_at(0.25) < 0.25);
+
+        // Keyframes at the same time replace each other
+        let replaced = eased.with_keyframe(1.0, 2.0, Easing::Linear);
+
+        assert_f64_near!(1.0, replaced.value_at(0.5));
+    }
+}
@@ -0,0 +1,213 @@
+use clap::Parser;
+use png::EncodingError;
+use ray_tracer_challenge::animation::easing::Easing;
+use ray_tracer_challenge::animation::track::Track;
+use ray_tracer_challenge::camera::Camera;
+use ray_tracer_challenge::canvas::{Canvas, PngOptions};
+use ray_tracer_challenge::color;
+use ray_tracer_challenge::color::Color;
+use ray_tracer_challenge::integrator::Integrator;
+use ray_tracer_challenge::integrator::phong::PhongIntegrator;
+use ray_tracer_challenge::light::SceneLight;
+use ray_tracer_challenge::light::point::PointLight;
+use ray_tracer_challenge::material::Material;
+use ray_tracer_challenge::random::Random;
+use ray_tracer_challenge::sampling::Sampler;
+use ray_tracer_challenge::sampling::filter::Filter;
+use ray_tracer_challenge::sampling::pattern::SamplePattern;
+use ray_tracer_challenge::shape::quad::Quad;
+use ray_tracer_challenge::shape::sphere::Sphere;
+use ray_tracer_challenge::transform::Transformation;
+use ray_tracer_challenge::vector::{Point, Vector};
+use ray_tracer_challenge::world::World;
+use std::f64::consts::PI;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+use std::time::Instant;
+
+const FLOOR_SIZE: f64 = 40.0;
+
+/// Renders an animation of a sphere bouncing across a floor as a sequence of numbered PNG frames.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Prefix for frame file names, which are numbered like `<OUT>0001.png`
+    #[arg(short, long)]
+    out: String,
+
+    #[arg(short, long, default_value_t = 256)]
+    size: usize,
+
+    /// Frames per second of animation
+    #[arg(long, default_value_t = 24.0)]
+    fps: f64,
+
+    /// First frame to render
+    #[arg(long, default_value_t = 0)]
+    start: usize,
+
+    /// Last frame to render, which defaults to the last frame of the animation
+    #[arg(long)]
+    end: Option<usize>,
+
+    /// Number of rays to shoot through each pixel
+    #[arg(long, default_value_t = 4)]
+    samples: usize,
+
+    /// Skip frames that have already been written, to pick up an interrupted render
+    #[arg(long)]
+    resume: bool,
+
+    /// Seed for randomized sample patterns
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+/// The keyframed parts of the scene.
+struct Scene {
+    camera_position: Track<Point>,
+    light_position: Track<Point>,
+    sphere_transformations: Track<Vec<Transformation>>,
+    sphere_color: Track<Color>,
+    sphere_shininess: Track<f64>,
+}
+
+impl Scene {
+    fn new() -> Self {
+        // The sphere drops in from one side, squashes against the floor and bounces off the other
+        // way
+        let sphere_at = |x: f64, y: f64, squash: f64| {
+            vec![
+                Transformation::Scale(1.0 + squash, 1.0 - squash, 1.0 + squash),
+                Transformation::Translate(x, y - squash, 0.0),
+            ]
+        };
+
+        Self {
+            camera_position: Track::new(0.0, Point::new(-2.0, 2.5, -9.0)).with_keyframe(
+                2.0,
+                Point::new(2.0, 2.0, -8.0),
+                Easing::EASE_IN_OUT,
+            ),
+            light_position: Track::new(0.0, Point::new(-10.0, 10.0, -10.0)).with_keyframe(
+                2.0,
+                Point::new(10.0, 10.0, -10.0),
+                Easing::Linear,
+            ),
+            sphere_transformations: Track::new(0.0, sphere_at(-3.0, 3.0, 0.0))
+                .with_keyframe(0.9, sphere_at(-0.2, 1.0, 0.0), Easing::EASE_IN)
+                .with_keyframe(1.0, sphere_at(0.0, 1.0, 0.2), Easing::EASE_OUT)
+                .with_keyframe(1.1, sphere_at(0.2, 1.0, 0.0), Easing::EASE_IN)
+                .with_keyframe(2.0, sphere_at(3.0, 3.0, 0.0), Easing::EASE_OUT),
+            sphere_color: Track::new(0.0, Color::new(1.0, 0.2, 1.0)).with_keyframe(
+                2.0,
+                Color::new(0.2, 0.6, 1.0),
+                Easing::EASE_IN_OUT,
+            ),
+            sphere_shininess: Track::new(0.0, 10.0).with_keyframe(2.0, 200.0, Easing::EASE_IN),
+        }
+    }
+
+    /// Returns the time of the last keyframe of any track.
+    fn duration(&self) -> f64 {
+        [
+            self.camera_position.end(),
+            self.light_position.end(),
+            self.sphere_transformations.end(),
+            self.sphere_color.end(),
+            self.sphere_shininess.end(),
+        ]
+        .into_iter()
+        .fold(0.0, f64::max)
+    }
+
+    fn world_at(&self, time: f64) -> World {
+        let mut world = World::new();
+
+        world.add_shape(Sphere::with_transformations(
+            &self.sphere_transformations.value_at(time),
+            Material::new(
+                self.sphere_color.value_at(time),
+                0.1,
+                0.9,
+                0.9,
+                self.sphere_shininess.value_at(time),
+            ),
+        ));
+
+        world.add_shape(Quad::new(
+            Point::new(-FLOOR_SIZE / 2.0, 0.0, -FLOOR_SIZE / 2.0),
+            Vector::new(FLOOR_SIZE, 0.0, 0.0),
+            Vector::new(0.0, 0.0, FLOOR_SIZE),
+            Material::new(color::WHITE, 0.1, 0.9, 0.0, 20.0),
+        ));
+
+        world.add_light(SceneLight::new(PointLight::new(
+            self.light_position.value_at(time),
+            color::WHITE,
+        )));
+
+        world
+    }
+
+    fn camera_at(&self, time: f64, size: usize) -> Camera {
+        Camera::new(size, size, PI / 3.0).with_look_at(
+            &self.camera_position.value_at(time),
+            &Point::new(0.0, 1.0, 0.0),
+            &Vector::new(0.0, 1.0, 0.0),
+        )
+    }
+}
+
+fn main() -> Result<(), EncodingError> {
+    let args = Args::parse();
+    let scene = Scene::new();
+
+    let last_frame = (scene.duration() * args.fps).round() as usize;
+    let end = args.end.unwrap_or(last_frame);
+
+    let sampler = Sampler::new(
+        args.samples,
+        SamplePattern::Jittered,
+        Filter::Box,
+        args.seed,
+    );
+
+    for frame in args.start..=end {
+        let path = format!("{}{:04}.png", args.out, frame);
+
+        if args.resume && Path::new(&path).exists() {
+            continue;
+        }
+
+        let start = Instant::now();
+        let time = frame as f64 / args.fps;
+        let world = scene.world_at(time);
+        let camera = scene.camera_at(time, args.size);
+
+        // Seed each frame on its own so that resumed renders match uninterrupted ones
+        let mut random = Random::new(args.seed.wrapping_add(frame as u64));
+        let mut canvas = Canvas::new(args.size, args.size);
+
+        let counts = sampler.render(&mut canvas, |x_canvas, y_canvas| {
+            camera
+                .ray_for_pixel(x_canvas, y_canvas, &mut random)
+                .and_then(|ray| PhongIntegrator.radiance(&world, &ray, &mut random))
+                .or(Some(color::BLACK))
+        });
+
+        let options = PngOptions::default()
+            .with_render_time(start.elapsed())
+            .with_samples_per_pixel(counts.mean().round() as usize);
+
+        // Write each frame beside its final path and move it into place once it's complete, so an
+        // interrupted write never leaves a partial frame for --resume to skip
+        let partial_path = format!("{path}.tmp");
+
+        canvas.write_as_png(File::create(&partial_path)?, &options)?;
+        fs::rename(&partial_path, &path)?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use clap::Parser;
 use png::EncodingError;
-use ray_tracer_challenge::canvas::Canvas;
+use ray_tracer_challenge::canvas::{Canvas, PngOptions};
 use ray_tracer_challenge::color::WHITE;
 use ray_tracer_challenge::transform;
 use ray_tracer_challenge::transform::Transformation;
@@ -33,5 +33,5 @@ fn main() -> Result<(), EncodingError> {
         canvas.set_pixel(x as usize, y as usize, WHITE);
     }
 
-    canvas.write_as_png(File::create(args.out)?, args.size as u32, args.size as u32)
+    canvas.write_as_png(File::create(args.out)?, &PngOptions::default())
 }
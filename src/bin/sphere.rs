@@ -1,17 +1,45 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use png::EncodingError;
-use ray_tracer_challenge::canvas::Canvas;
+use ray_tracer_challenge::animation::easing::Easing;
+use ray_tracer_challenge::animation::track::Track;
+use ray_tracer_challenge::camera::{Aperture, Camera, ThinLens};
+use ray_tracer_challenge::canvas::output::{OutputTransform, ToneMapping, TransferFunction};
+use ray_tracer_challenge::canvas::{Canvas, PngBitDepth, PngOptions};
 use ray_tracer_challenge::color;
 use ray_tracer_challenge::color::Color;
-use ray_tracer_challenge::intersection;
-use ray_tracer_challenge::light::PointLight;
+use ray_tracer_challenge::integrator::Integrator;
+use ray_tracer_challenge::integrator::path::PathIntegrator;
+use ray_tracer_challenge::integrator::phong::PhongIntegrator;
+use ray_tracer_challenge::light::SceneLight;
+use ray_tracer_challenge::light::area::{AreaLight, AreaLightShape};
+use ray_tracer_challenge::light::directional::DirectionalLight;
+use ray_tracer_challenge::light::point::PointLight;
+use ray_tracer_challenge::light::power::Power;
+use ray_tracer_challenge::light::spot::SpotLight;
 use ray_tracer_challenge::material::Material;
-use ray_tracer_challenge::ray::Ray;
-use ray_tracer_challenge::shape::Shape;
+use ray_tracer_challenge::material::microfacet::Microfacet;
+use ray_tracer_challenge::material::shading::{BlinnPhong, Lambert, OrenNayar};
+use ray_tracer_challenge::material::subsurface::Subsurface;
+use ray_tracer_challenge::projection::equirectangular::Equirectangular;
+use ray_tracer_challenge::projection::fisheye::Fisheye;
+use ray_tracer_challenge::projection::orthographic::Orthographic;
+use ray_tracer_challenge::random::Random;
+use ray_tracer_challenge::sampling::filter::Filter;
+use ray_tracer_challenge::sampling::pattern::SamplePattern;
+use ray_tracer_challenge::sampling::{AdaptiveSampling, Sampler};
+use ray_tracer_challenge::shape::quad::Quad;
 use ray_tracer_challenge::shape::sphere::Sphere;
+use ray_tracer_challenge::stereo::{Convergence, StereoLayout, StereoRig};
 use ray_tracer_challenge::transform::Transformation;
-use ray_tracer_challenge::vector::Point;
+use ray_tracer_challenge::vector::{Point, Vector};
+use ray_tracer_challenge::volume::Volume;
+use ray_tracer_challenge::volume::fog::Fog;
+use ray_tracer_challenge::volume::medium::HomogeneousMedium;
+use ray_tracer_challenge::volume::phase::HenyeyGreenstein;
+use ray_tracer_challenge::world::World;
+use std::f64::consts::PI;
 use std::fs::File;
+use std::time::Instant;
 
 const BACKDROP_WIDTH: f64 = 8.0;
 const BACKDROP_HEIGHT: f64 = 8.0;
@@ -27,50 +55,526 @@ struct Args {
 
     #[arg(short, long, default_value_t = 1024)]
     size: usize,
+
+    /// Exposure adjustment in stops
+    #[arg(short, long, default_value_t = 0.0, allow_negative_numbers = true)]
+    exposure: f64,
+
+    #[arg(short, long, value_enum, default_value_t = ToneMappingArg::Clamp)]
+    tone_mapping: ToneMappingArg,
+
+    /// Write 16 bits per channel instead of 8
+    #[arg(long)]
+    deep: bool,
+
+    /// Leave pixels that miss the sphere transparent instead of black
+    #[arg(long)]
+    transparent: bool,
+
+    /// Number of rays to shoot through each pixel (the first batch, with adaptive sampling)
+    #[arg(long, default_value_t = 1)]
+    samples: usize,
+
+    /// Keep adding batches of samples to noisy pixels, up to this many samples per pixel
+    #[arg(long)]
+    max_samples: Option<usize>,
+
+    /// Stop sampling a pixel once the variance of its mean luminance falls below this value
+    #[arg(long, default_value_t = 1e-4)]
+    variance_threshold: f64,
+
+    /// Also write a heatmap of the number of samples taken within each pixel
+    #[arg(long)]
+    heatmap: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = PatternArg::Grid)]
+    pattern: PatternArg,
+
+    #[arg(long, value_enum, default_value_t = FilterArg::Box)]
+    filter: FilterArg,
+
+    /// Seed for randomized sample patterns
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    #[arg(long, value_enum, default_value_t = LightArg::Point)]
+    light: LightArg,
+
+    /// Width of a rectangular area light or diameter of a spherical one
+    #[arg(long, default_value_t = 2.0)]
+    light_size: f64,
+
+    /// Emit this many watts with inverse-square falloff instead of a unit intensity with no
+    /// falloff (ignored by directional lights)
+    #[arg(long)]
+    watts: Option<f64>,
+
+    /// Angle in degrees between a spot light's axis and the edge of its cone
+    #[arg(long, default_value_t = 10.0)]
+    spot_angle: f64,
+
+    /// Number of shadow rays to cast toward area lights for each shading point
+    #[arg(long, default_value_t = 16)]
+    light_samples: usize,
+
+    /// Place a wall behind the sphere to catch its shadow
+    #[arg(long)]
+    wall: bool,
+
+    /// Add a dim fill light, which casts no shadows, opposite the main light
+    #[arg(long)]
+    fill: bool,
+
+    /// Add a glowing panel beside the sphere that lights the scene
+    #[arg(long)]
+    panel: bool,
+
+    /// Fill the scene with fog of the given density
+    #[arg(long)]
+    fog: Option<f64>,
+
+    /// Surround the sphere with smoke that scatters the given fraction of light per unit
+    /// distance
+    #[arg(long)]
+    smoke: Option<f64>,
+
+    #[arg(long, value_enum, default_value_t = IntegratorArg::Phong)]
+    integrator: IntegratorArg,
+
+    #[arg(long, value_enum, default_value_t = MaterialArg::Phong)]
+    material: MaterialArg,
+
+    /// Surface roughness of physically-based and Oren-Nayar sphere materials, from 0 (smooth)
+    /// to 1
+    #[arg(long, default_value_t = 0.3)]
+    roughness: f64,
+
+    /// Maximum number of bounces for the path integrator
+    #[arg(long, default_value_t = 16)]
+    max_depth: usize,
+
+    /// Radius of the camera's lens aperture; larger apertures blur more of the scene
+    #[arg(long, default_value_t = 0.0)]
+    aperture: f64,
+
+    /// Distance from the camera to the plane in sharp focus
+    #[arg(long, default_value_t = -CAMERA_Z)]
+    focal_distance: f64,
+
+    /// Number of diaphragm blades shaping the aperture, which is round if unset
+    #[arg(long)]
+    blades: Option<usize>,
+
+    /// Distance the sphere moves sideways while the shutter is open, blurring it
+    #[arg(long)]
+    motion: Option<f64>,
+
+    /// Render a stereo pair for both eyes, combined as given
+    #[arg(long, value_enum)]
+    stereo: Option<StereoArg>,
+
+    /// Distance between the eyes of a stereo pair, which converge at the focal distance
+    #[arg(long, default_value_t = 0.2)]
+    interocular: f64,
+
+    /// Rotate the eyes of a stereo pair inward rather than shifting their views
+    #[arg(long)]
+    toe_in: bool,
+
+    /// How the camera maps the scene onto the canvas. Equirectangular panoramas are twice as
+    /// wide as they are tall.
+    #[arg(long, value_enum, default_value_t = ProjectionArg::Perspective)]
+    projection: ProjectionArg,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum MaterialArg {
+    Phong,
+    Blinn,
+    Lambert,
+    OrenNayar,
+    Plastic,
+    Metal,
+    Wax,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum ProjectionArg {
+    Perspective,
+    Orthographic,
+    Fisheye,
+    Equirectangular,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum StereoArg {
+    SideBySide,
+    OverUnder,
+    Anaglyph,
+}
+
+impl From<StereoArg> for StereoLayout {
+    fn from(stereo: StereoArg) -> Self {
+        match stereo {
+            StereoArg::SideBySide => StereoLayout::SideBySide,
+            StereoArg::OverUnder => StereoLayout::OverUnder,
+            StereoArg::Anaglyph => StereoLayout::Anaglyph,
+        }
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum IntegratorArg {
+    Phong,
+    Path,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum LightArg {
+    Point,
+    Directional,
+    Spot,
+    Rectangle,
+    Sphere,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum PatternArg {
+    Grid,
+    Jittered,
+    Halton,
+    Sobol,
+}
+
+impl From<PatternArg> for SamplePattern {
+    fn from(pattern: PatternArg) -> Self {
+        match pattern {
+            PatternArg::Grid => SamplePattern::Grid,
+            PatternArg::Jittered => SamplePattern::Jittered,
+            PatternArg::Halton => SamplePattern::Halton,
+            PatternArg::Sobol => SamplePattern::Sobol,
+        }
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum FilterArg {
+    Box,
+    Tent,
+    Gaussian,
+    Mitchell,
+}
+
+impl From<FilterArg> for Filter {
+    fn from(filter: FilterArg) -> Self {
+        match filter {
+            FilterArg::Box => Filter::Box,
+            FilterArg::Tent => Filter::Tent,
+            FilterArg::Gaussian => Filter::Gaussian {
+                radius: 1.5,
+                alpha: 2.0,
+            },
+            FilterArg::Mitchell => Filter::MitchellNetravali {
+                b: 1.0 / 3.0,
+                c: 1.0 / 3.0,
+            },
+        }
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum ToneMappingArg {
+    Clamp,
+    Reinhard,
+    Aces,
+}
+
+impl From<ToneMappingArg> for ToneMapping {
+    fn from(tone_mapping: ToneMappingArg) -> Self {
+        match tone_mapping {
+            ToneMappingArg::Clamp => ToneMapping::Clamp,
+            ToneMappingArg::Reinhard => ToneMapping::Reinhard,
+            ToneMappingArg::Aces => ToneMapping::AcesFilmic,
+        }
+    }
 }
 
 fn main() -> Result<(), EncodingError> {
     let args = Args::parse();
+    let start = Instant::now();
+
+    let sphere_color = Color::new(1.0, 0.2, 1.0);
+
+    let material = match args.material {
+        MaterialArg::Phong => Material::new(sphere_color, 0.1, 0.9, 0.9, 20.0),
+        MaterialArg::Blinn => {
+            Material::new(sphere_color, 0.1, 0.9, 0.9, 80.0).with_shading(BlinnPhong)
+        }
+        MaterialArg::Lambert => {
+            Material::new(sphere_color, 0.1, 0.9, 0.0, 20.0).with_shading(Lambert)
+        }
+        MaterialArg::OrenNayar => Material::new(sphere_color, 0.1, 0.9, 0.0, 20.0)
+            .with_shading(OrenNayar::new(args.roughness)),
+        MaterialArg::Plastic => {
+            Material::physical(Microfacet::new(sphere_color, 0.0, args.roughness, 1.5))
+        }
+        MaterialArg::Metal => {
+            Material::physical(Microfacet::new(sphere_color, 1.0, args.roughness, 1.5))
+        }
+        MaterialArg::Wax => Material::new(sphere_color, 0.1, 0.1, 0.3, 50.0).with_subsurface(
+            Subsurface::new(Color::new(0.99, 0.95, 0.9), Color::new(0.4, 0.2, 0.1)),
+        ),
+    };
 
-    let sphere = Sphere::with_transformations(
-        &[Transformation::Translate(
-            BACKDROP_WIDTH / 2.0,
+    let sphere_at = |x_offset: f64| {
+        vec![Transformation::Translate(
+            (BACKDROP_WIDTH / 2.0) + x_offset,
             BACKDROP_HEIGHT / 2.0,
             0.0,
-        )],
-        Material::new(Color::new(1.0, 0.2, 1.0), 0.1, 0.9, 0.9, 20.0),
+        )]
+    };
+
+    let sphere = match args.motion {
+        Some(distance) => Sphere::with_keyframes(
+            Track::new(0.0, sphere_at(-distance / 2.0)).with_keyframe(
+                1.0,
+                sphere_at(distance / 2.0),
+                Easing::Linear,
+            ),
+            material,
+        ),
+        None => Sphere::with_transformations(&sphere_at(0.0), material),
+    };
+
+    let mut world = World::new();
+    world.add_shape(sphere);
+
+    if args.wall {
+        world.add_shape(Sphere::with_transformations(
+            &[
+                Transformation::Scale(BACKDROP_WIDTH * 4.0, BACKDROP_HEIGHT * 4.0, 0.01),
+                Transformation::Translate(BACKDROP_WIDTH / 2.0, BACKDROP_HEIGHT / 2.0, BACKDROP_Z),
+            ],
+            Material::new(color::WHITE, 0.1, 0.9, 0.0, 20.0),
+        ));
+    }
+
+    if args.panel {
+        world.add_shape(Quad::new(
+            Point::new(BACKDROP_WIDTH * 0.75, BACKDROP_HEIGHT * 0.2, 1.0),
+            Vector::new(0.0, BACKDROP_HEIGHT * 0.6, 0.0),
+            Vector::new(BACKDROP_WIDTH * 0.05, 0.0, 0.0),
+            Material::default().with_emission(Color::new(1.0, 0.3, 0.8)),
+        ));
+    }
+
+    world.set_emitter_samples(args.light_samples);
+
+    if let Some(density) = args.fog {
+        world.set_fog(Some(Fog::new(Color::new(0.5, 0.5, 0.55), density)));
+    }
+
+    if let Some(scattering) = args.smoke {
+        world.add_volume(Volume::new(
+            Sphere::with_transformations(
+                &[
+                    Transformation::Scale(3.0, 3.0, 3.0),
+                    Transformation::Translate(BACKDROP_WIDTH / 2.0, BACKDROP_HEIGHT / 2.0, 0.0),
+                ],
+                Material::default(),
+            ),
+            HomogeneousMedium::new(scattering * 0.1, scattering)
+                .with_phase(HenyeyGreenstein::new(0.3)),
+        ));
+    }
+
+    let width = match args.projection {
+        ProjectionArg::Equirectangular => args.size * 2,
+        _ => args.size,
+    };
+
+    let mut canvas = Canvas::new(width, args.size);
+
+    // Frame the backdrop exactly, with y increasing down the canvas as the scene was laid out
+    let field_of_view = 2.0 * ((BACKDROP_WIDTH / 2.0) / (BACKDROP_Z - CAMERA_Z)).atan();
+    let camera = Camera::new(width, args.size, field_of_view);
+
+    let mut camera = match args.projection {
+        ProjectionArg::Perspective => camera,
+        ProjectionArg::Orthographic => camera.with_projection(Orthographic::new(BACKDROP_WIDTH)),
+        ProjectionArg::Fisheye => camera.with_projection(Fisheye::new(PI)),
+        ProjectionArg::Equirectangular => camera.with_projection(Equirectangular),
+    }
+    .with_look_at(
+        &Point::new(BACKDROP_WIDTH / 2.0, BACKDROP_HEIGHT / 2.0, CAMERA_Z),
+        &Point::new(BACKDROP_WIDTH / 2.0, BACKDROP_HEIGHT / 2.0, 0.0),
+        &Vector::new(0.0, -1.0, 0.0),
+    );
+
+    if args.aperture > 0.0 {
+        let aperture = match args.blades {
+            Some(blades) => Aperture::Polygon {
+                blades,
+                rotation: 0.0,
+            },
+            None => Aperture::Circle,
+        };
+
+        camera = camera
+            .with_lens(ThinLens::new(args.aperture, args.focal_distance).with_aperture(aperture));
+    }
+
+    if args.motion.is_some() {
+        camera = camera.with_shutter(0.0, 1.0);
+    }
+
+    let light_position = Point::new(0.0, 0.0, CAMERA_Z * 2.0);
+    let sphere_center = Point::new(BACKDROP_WIDTH / 2.0, BACKDROP_HEIGHT / 2.0, 0.0);
+
+    let power = args.watts.map(Power::Watts);
+    let light_direction = &sphere_center - &light_position;
+    let (spot_inner, spot_outer) = (
+        (args.spot_angle / 2.0).to_radians(),
+        args.spot_angle.to_radians(),
     );
 
-    let camera = Point::new(BACKDROP_WIDTH / 2.0, BACKDROP_HEIGHT / 2.0, CAMERA_Z);
-    let mut canvas = Canvas::new(args.size, args.size);
+    let area_shape = match args.light {
+        LightArg::Sphere => AreaLightShape::Sphere {
+            center: light_position,
+            radius: args.light_size / 2.0,
+        },
+        _ => AreaLightShape::Rectangle {
+            corner: light_position
+                - &Vector::new(args.light_size / 2.0, args.light_size / 2.0, 0.0),
+            u: Vector::new(args.light_size, 0.0, 0.0),
+            v: Vector::new(0.0, args.light_size, 0.0),
+        },
+    };
 
-    let light = PointLight::new(Point::new(0.0, 0.0, CAMERA_Z * 2.0), color::WHITE);
+    let light = match (args.light, power) {
+        (LightArg::Point, None) => SceneLight::new(PointLight::new(light_position, color::WHITE)),
+        (LightArg::Point, Some(power)) => {
+            SceneLight::new(PointLight::with_power(light_position, color::WHITE, power))
+        }
+        (LightArg::Directional, _) => {
+            SceneLight::new(DirectionalLight::new(light_direction, color::WHITE))
+        }
+        (LightArg::Spot, None) => SceneLight::new(SpotLight::new(
+            light_position,
+            light_direction,
+            spot_inner,
+            spot_outer,
+            color::WHITE,
+        )),
+        (LightArg::Spot, Some(power)) => SceneLight::new(SpotLight::with_power(
+            light_position,
+            light_direction,
+            spot_inner,
+            spot_outer,
+            color::WHITE,
+            power,
+        )),
+        (_, None) => SceneLight::new(AreaLight::new(area_shape, color::WHITE, args.light_samples)),
+        (_, Some(power)) => SceneLight::new(AreaLight::with_power(
+            area_shape,
+            color::WHITE,
+            power,
+            args.light_samples,
+        )),
+    };
 
-    for x_canvas in 0..canvas.width() {
-        for y_canvas in 0..canvas.height() {
-            let x_world = (x_canvas as f64 / canvas.width() as f64) * BACKDROP_WIDTH;
-            let y_world = (y_canvas as f64 / canvas.height() as f64) * BACKDROP_HEIGHT;
+    world.add_light(light);
 
-            let target = Point::new(x_world, y_world, BACKDROP_Z);
-            let camera_to_target = (&target - &camera).normalize();
-            let ray = Ray::new(camera, camera_to_target);
+    if args.fill {
+        world.add_light(
+            SceneLight::new(PointLight::new(
+                Point::new(BACKDROP_WIDTH, BACKDROP_HEIGHT, CAMERA_Z * 2.0),
+                Color::new(0.3, 0.3, 0.3),
+            ))
+            .with_shadows(false),
+        );
+    }
 
-            let color = if let Some(intersection) = intersection::hit(&sphere.intersect(&ray)) {
-                let position = ray.position(intersection.distance());
+    let integrator: Box<dyn Integrator> = match args.integrator {
+        IntegratorArg::Phong => Box::new(PhongIntegrator),
+        IntegratorArg::Path => Box::new(PathIntegrator::new(
+            args.max_depth,
+            PathIntegrator::default().roulette_depth(),
+        )),
+    };
 
-                sphere.material().lighting(
-                    &light,
-                    &position,
-                    &-camera_to_target,
-                    &intersection.shape().normal_at(&position),
-                )
+    let mut random = Random::new(args.seed);
+
+    let mut sampler = Sampler::new(
+        args.samples,
+        args.pattern.into(),
+        args.filter.into(),
+        args.seed,
+    );
+
+    if let Some(max_samples) = args.max_samples {
+        sampler = sampler
+            .with_adaptive_sampling(AdaptiveSampling::new(max_samples, args.variance_threshold));
+    }
+
+    let mut render = |camera: &Camera, canvas: &mut Canvas| {
+        sampler.render(canvas, |x_canvas, y_canvas| {
+            let radiance = camera
+                .ray_for_pixel(x_canvas, y_canvas, &mut random)
+                .and_then(|ray| integrator.radiance(&world, &ray, &mut random));
+
+            match radiance {
+                Some(color) => Some(color),
+                None if args.transparent => None,
+                None => Some(color::BLACK),
+            }
+        })
+    };
+
+    // Stereo renders report the left eye's sample counts
+    let counts = match args.stereo {
+        Some(layout) => {
+            let convergence = if args.toe_in {
+                Convergence::ToeIn
             } else {
-                color::BLACK
+                Convergence::OffAxis
             };
 
-            canvas.set_pixel(x_canvas, y_canvas, color);
+            let (left, right) = StereoRig::new(args.interocular, args.focal_distance)
+                .with_convergence(convergence)
+                .eyes(&camera);
+
+            let mut right_canvas = Canvas::new(canvas.width(), canvas.height());
+            let counts = render(&left, &mut canvas);
+            render(&right, &mut right_canvas);
+
+            canvas = StereoLayout::from(layout).compose(&canvas, &right_canvas);
+            counts
         }
+        None => render(&camera, &mut canvas),
+    };
+
+    let transform = OutputTransform::new(
+        args.exposure,
+        args.tone_mapping.into(),
+        TransferFunction::Srgb,
+    );
+
+    let options = PngOptions::default()
+        .with_bit_depth(if args.deep {
+            PngBitDepth::Sixteen
+        } else {
+            PngBitDepth::Eight
+        })
+        .with_transform(transform)
+        .with_render_time(start.elapsed())
+        .with_samples_per_pixel(counts.mean().round() as usize);
+
+    if let Some(heatmap) = &args.heatmap {
+        counts
+            .heatmap()
+            .write_as_png(File::create(heatmap)?, &PngOptions::default())?;
     }
 
-    canvas.write_as_png(File::create(args.out)?, args.size as u32, args.size as u32)
+    canvas.write_as_png(File::create(args.out)?, &options)
 }
@@ -0,0 +1,360 @@
+use crate::matrix::Matrix;
+use crate::projection::Projection;
+use crate::projection::perspective::Perspective;
+use crate::random::Random;
+use crate::ray::Ray;
+use crate::sampling::warp::{uniform_sample_disc, uniform_sample_polygon};
+use crate::transform::Transformation;
+use crate::vector::{Point, Vector};
+use std::sync::Arc;
+
+/// The shape of a lens's aperture, which determines the shape of out-of-focus highlights
+/// (bokeh).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Aperture {
+    Circle,
+    /// A regular polygon, like the opening formed by a lens's diaphragm blades, with its first
+    /// corner `rotation` radians counterclockwise from the camera's right.
+    Polygon {
+        blades: usize,
+        rotation: f64,
+    },
+}
+
+/// A thin lens that brings everything at `focal_distance` from the camera into sharp focus and
+/// blurs everything else, more so for larger apertures.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ThinLens {
+    aperture_radius: f64,
+    focal_distance: f64,
+    aperture: Aperture,
+}
+
+impl ThinLens {
+    pub fn new(aperture_radius: f64, focal_distance: f64) -> Self {
+        Self {
+            aperture_radius,
+            focal_distance,
+            aperture: Aperture::Circle,
+        }
+    }
+
+    pub fn with_aperture(mut self, aperture: Aperture) -> Self {
+        self.aperture = aperture;
+        self
+    }
+
+    pub fn aperture_radius(&self) -> f64 {
+        self.aperture_radius
+    }
+
+    pub fn focal_distance(&self) -> f64 {
+        self.focal_distance
+    }
+
+    pub fn aperture(&self) -> &Aperture {
+        &self.aperture
+    }
+
+    /// Returns a point chosen uniformly at random on the lens's aperture, relative to its
+    /// center.
+    fn sample_aperture(&self, random: &mut Random) -> (f64, f64) {
+        let (x, y) = match self.aperture {
+            Aperture::Circle => uniform_sample_disc(random),
+            Aperture::Polygon { blades, rotation } => {
+                uniform_sample_polygon(blades, rotation, random)
+            }
+        };
+
+        (x * self.aperture_radius, y * self.aperture_radius)
+    }
+}
+
+/// A camera that maps a canvas of `width` × `height` pixels onto the world through a
+/// projection. In its own coordinate space, the camera sits at the origin looking toward -z with
+/// +y up.
+#[derive(Clone)]
+pub struct Camera {
+    width: usize,
+    height: usize,
+    projection: Arc<dyn Projection + Send + Sync>,
+    transformation: Matrix<4>,
+    lens: Option<ThinLens>,
+    shutter: (f64, f64),
+}
+
+impl Camera {
+    /// Creates a pinhole perspective camera whose field of view, in radians, spans the longer
+    /// side of the canvas.
+    pub fn new(width: usize, height: usize, field_of_view: f64) -> Self {
+        Self {
+            width,
+            height,
+            projection: Arc::new(Perspective::new(field_of_view)),
+            transformation: Matrix::<4>::identity(),
+            lens: None,
+            shutter: (0.0, 0.0),
+        }
+    }
+
+    /// Replaces the camera's perspective projection.
+    pub fn with_projection<P: Projection + Send + Sync + 'static>(mut self, projection: P) -> Self {
+        self.projection = Arc::new(projection);
+        self
+    }
+
+    /// Places the camera at `from`, looking toward `to`, with `up` pointing roughly toward the
+    /// top of the canvas.
+    pub fn with_look_at(mut self, from: &Point, to: &Point, up: &Vector) -> Self {
+        self.transformation = Matrix::from(&Transformation::LookAt {
+            from: *from,
+            to: *to,
+            up: *up,
+        });
+
+        self
+    }
+
+    /// Replaces the matrix that transforms points from the camera's space to world space.
+    pub fn with_transformation(mut self, transformation: Matrix<4>) -> Self {
+        self.transformation = transformation;
+        self
+    }
+
+    /// Replaces the camera's pinhole with a thin lens, giving it a limited depth of field.
+    pub fn with_lens(mut self, lens: ThinLens) -> Self {
+        self.lens = Some(lens);
+        self
+    }
+
+    /// Keeps the camera's shutter open from time `open` until time `close`, so that each ray
+    /// samples the scene at a random moment in between and moving shapes blur.
+    pub fn with_shutter(mut self, open: f64, close: f64) -> Self {
+        self.shutter = (open, close);
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn projection(&self) -> &dyn Projection {
+        self.projection.as_ref()
+    }
+
+    /// Returns the matrix that transforms points from the camera's space to world space.
+    pub fn transformation(&self) -> &Matrix<4> {
+        &self.transformation
+    }
+
+    pub fn lens(&self) -> Option<&ThinLens> {
+        self.lens.as_ref()
+    }
+
+    /// Returns the times at which the camera's shutter opens and closes.
+    pub fn shutter(&self) -> (f64, f64) {
+        self.shutter
+    }
+
+    /// Returns a ray from the camera through the point (`x`, `y`) on the canvas, where (0, 0) is
+    /// the top left corner of the canvas and (`width`, `height`) is the bottom right, or `None`
+    /// if the camera's projection doesn't cover that point. Cameras with a lens start rays at
+    /// random points on the lens's aperture, and cameras with an open shutter at random times.
+    pub fn ray_for_pixel(&self, x: f64, y: f64, random: &mut Random) -> Option<Ray> {
+        let (origin, direction) = self.projection.ray(
+            x / self.width as f64,
+            y / self.height as f64,
+            self.width as f64 / self.height as f64,
+        )?;
+
+        let (origin, direction) = match &self.lens {
+            // Rays through the same canvas point converge on the plane of focus. Rays that
+            // don't head toward it (from very wide projections) pass through the lens's center.
+            Some(lens) if direction.components()[2] < 0.0 => {
+                let focus =
+                    origin + &(direction * (lens.focal_distance / -direction.components()[2]));
+                let (lens_x, lens_y) = lens.sample_aperture(random);
+                let origin = origin + &Vector::new(lens_x, lens_y, 0.0);
+
+                (origin, focus - &origin)
+            }
+            _ => (origin, direction),
+        };
+
+        let (open, close) = self.shutter;
+
+        let time = if close > open {
+            open + ((close - open) * random.next_f64())
+        } else {
+            open
+        };
+
+        Some(
+            Ray::new(
+                &self.transformation * &origin,
+                (&self.transformation * &direction).normalize(),
+            )
+            .with_time(time),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::camera::{Aperture, Camera, ThinLens};
+    use crate::projection::fisheye::Fisheye;
+    use crate::projection::orthographic::Orthographic;
+    use crate::random::Random;
+    use crate::vector::{Point, Vector};
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_ray_for_pixel() {
+        let camera = Camera::new(201, 101, PI / 2.0);
+        let mut random = Random::new(0);
+
+        // Through the center of the canvas
+        let ray = camera.ray_for_pixel(100.5, 50.5, &mut random).unwrap();
+
+        Point::new(0.0, 0.0, 0.0).assert_approx_eq(ray.origin());
+        Vector::new(0.0, 0.0, -1.0).assert_approx_eq_epsilon(ray.direction(), 1e-12);
+
+        // Through the top left corner
+        let ray = camera.ray_for_pixel(0.0, 0.0, &mut random).unwrap();
+
+        Vector::new(-1.0, 101.0 / 201.0, -1.0)
+            .normalize()
+            .assert_approx_eq_epsilon(ray.direction(), 1e-12);
+    }
+
+    #[test]
+    fn test_look_at() {
+        let camera = Camera::new(201, 101, PI / 2.0).with_look_at(
+            &Point::new(0.0, 2.0, -5.0),
+            &Point::new(0.0, 2.0, 0.0),
+            &Vector::new(0.0, 1.0, 0.0),
+        );
+        let mut random = Random::new(0);
+
+        let ray = camera.ray_for_pixel(100.5, 50.5, &mut random).unwrap();
+
+        Point::new(0.0, 2.0, -5.0).assert_approx_eq(ray.origin());
+        Vector::new(0.0, 0.0, 1.0).assert_approx_eq_epsilon(ray.direction(), 1e-12);
+
+        // Looking toward +z with +y up puts +x on the left of the canvas
+        let ray = camera.ray_for_pixel(0.0, 50.5, &mut random).unwrap();
+
+        assert!(ray.direction().components()[0] > 0.0);
+    }
+
+    #[test]
+    fn test_thin_lens() {
+        let lens = ThinLens::new(0.5, 4.0);
+        let camera = Camera::new(100, 100, PI / 3.0).with_lens(lens);
+        let mut random = Random::new(0);
+
+        // Rays through the same point on the canvas start at different points on the lens but
+        // meet on the plane of focus
+        let focus = |camera: &Camera, random: &mut Random| {
+            let ray = camera.ray_for_pixel(30.0, 70.0, random).unwrap();
+            let distance = -4.0 / ray.direction().components()[2];
+
+            (ray.origin().coordinates(), ray.position(distance))
+        };
+
+        let ((x, y, z), expected) = focus(&camera, &mut random);
+
+        assert_f64_near!(0.0, z);
+        assert!((x * x) + (y * y) <= 0.25);
+
+        for _ in 0..100 {
+            let (_, point) = focus(&camera, &mut random);
+
+            expected.assert_approx_eq_epsilon(&point, 1e-9);
+        }
+
+        // Polygonal apertures keep rays within the polygon
+        let camera =
+            Camera::new(100, 100, PI / 3.0).with_lens(lens.with_aperture(Aperture::Polygon {
+                blades: 4,
+                rotation: 0.0,
+            }));
+
+        for _ in 0..100 {
+            let (x, y, _) = camera
+                .ray_for_pixel(50.0, 50.0, &mut random)
+                .unwrap()
+                .origin()
+                .coordinates();
+
+            assert!(x.abs() + y.abs() <= 0.5 + 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_projection() {
+        let mut random = Random::new(0);
+
+        // Projections work in the camera's space
+        let camera = Camera::new(100, 50, PI / 2.0)
+            .with_projection(Orthographic::new(10.0))
+            .with_look_at(
+                &Point::new(0.0, 0.0, 5.0),
+                &Point::new(0.0, 0.0, 0.0),
+                &Vector::new(0.0, 1.0, 0.0),
+            );
+
+        let ray = camera.ray_for_pixel(0.0, 0.0, &mut random).unwrap();
+
+        Point::new(-5.0, 2.5, 5.0).assert_approx_eq(ray.origin());
+        Vector::new(0.0, 0.0, -1.0).assert_approx_eq_epsilon(ray.direction(), 1e-12);
+
+        // Points outside a fisheye's image circle have no rays
+        let camera = Camera::new(100, 100, PI / 2.0).with_projection(Fisheye::new(PI));
+
+        assert!(camera.ray_for_pixel(50.0, 50.0, &mut random).is_some());
+        assert!(camera.ray_for_pixel(0.0, 0.0, &mut random).is_none());
+    }
+
+    #[test]
+    fn test_shutter() {
+        let mut random = Random::new(0);
+        let camera = Camera::new(100, 100, PI / 2.0);
+
+        assert_eq!(
+            0.0,
+            camera
+                .ray_for_pixel(50.0, 50.0, &mut random)
+                .unwrap()
+                .time()
+        );
+
+        let camera = camera.with_shutter(1.0, 1.5);
+        let mut sum = 0.0;
+
+        for _ in 0..1_000 {
+            let time = camera
+                .ray_for_pixel(50.0, 50.0, &mut random)
+                .unwrap()
+                .time();
+
+            assert!((1.0..1.5).contains(&time));
+            sum += time;
+        }
+
+        assert_float_absolute_eq!(1.25, sum / 1_000.0, 0.02);
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        // Cameras can be shared between rendering threads
+        assert_send_sync::<Camera>();
+    }
+}
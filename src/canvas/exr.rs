@@ -0,0 +1,351 @@
+use crate::canvas::Canvas;
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use std::io;
+use std::io::{BufWriter, Write};
+
+const MAGIC: [u8; 4] = [0x76, 0x2f, 0x31, 0x01];
+const VERSION: u32 = 2;
+
+// Pixel type identifier for 32-bit IEEE floats
+const PIXEL_TYPE_FLOAT: i32 = 2;
+
+// Channels must appear in alphabetical order both in the header and in each scanline
+const CHANNELS: [(&str, usize); 3] = [("B", 2), ("G", 1), ("R", 0)];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ExrCompression {
+    None,
+    Zip,
+}
+
+impl ExrCompression {
+    fn id(&self) -> u8 {
+        match self {
+            ExrCompression::None => 0,
+            ExrCompression::Zip => 3,
+        }
+    }
+
+    fn scanlines_per_block(&self) -> usize {
+        match self {
+            ExrCompression::None => 1,
+            ExrCompression::Zip => 16,
+        }
+    }
+}
+
+impl Canvas {
+    /// Writes this canvas as a single-part, scanline OpenEXR image with full 32-bit float `R`,
+    /// `G` and `B` channels.
+    pub fn write_as_exr<W: Write>(&self, writer: W, compression: ExrCompression) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        let header = self.exr_header(compression);
+
+        let blocks: Vec<(usize, Vec<u8>)> = (0..self.height())
+            .step_by(compression.scanlines_per_block())
+            .map(|y| {
+                let rows = compression.scanlines_per_block().min(self.height() - y);
+                let raw = self.exr_scanlines(y, rows);
+
+                let data = match compression {
+                    ExrCompression::None => raw,
+                    ExrCompression::Zip => zip_compress(&raw)?,
+                };
+
+                Ok((y, data))
+            })
+            .collect::<io::Result<_>>()?;
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&header)?;
+
+        // The offset table holds the absolute file position of each block
+        let mut offset = (MAGIC.len() + 4 + header.len() + (blocks.len() * 8)) as u64;
+
+        for (_, data) in &blocks {
+            writer.write_all(&offset.to_le_bytes())?;
+            offset += 8 + data.len() as u64;
+        }
+
+        for (y, data) in &blocks {
+            writer.write_all(&(*y as i32).to_le_bytes())?;
+            writer.write_all(&(data.len() as i32).to_le_bytes())?;
+            writer.write_all(data)?;
+        }
+
+        writer.flush()
+    }
+
+    fn exr_header(&self, compression: ExrCompression) -> Vec<u8> {
+        let mut header = Vec::new();
+
+        let mut channels = Vec::new();
+
+        for (name, _) in CHANNELS {
+            channels.extend_from_slice(name.as_bytes());
+            channels.push(0);
+            channels.extend_from_slice(&PIXEL_TYPE_FLOAT.to_le_bytes());
+            // pLinear and three reserved bytes
+            channels.extend_from_slice(&[0, 0, 0, 0]);
+            // x and y sampling
+            channels.extend_from_slice(&1i32.to_le_bytes());
+            channels.extend_from_slice(&1i32.to_le_bytes());
+        }
+
+        channels.push(0);
+
+        let mut window = Vec::new();
+
+        for bound in [0, 0, self.width as i32 - 1, self.height() as i32 - 1] {
+            window.extend_from_slice(&bound.to_le_bytes());
+        }
+
+        write_attribute(&mut header, "channels", "chlist", &channels);
+        write_attribute(
+            &mut header,
+            "compression",
+            "compression",
+            &[compression.id()],
+        );
+        write_attribute(&mut header, "dataWindow", "box2i", &window);
+        write_attribute(&mut header, "displayWindow", "box2i", &window);
+        write_attribute(&mut header, "lineOrder", "lineOrder", &[0]);
+        write_attribute(
+            &mut header,
+            "pixelAspectRatio",
+            "float",
+            &1.0f32.to_le_bytes(),
+        );
+        write_attribute(&mut header, "screenWindowCenter", "v2f", &[0; 8]);
+        write_attribute(
+            &mut header,
+            "screenWindowWidth",
+            "float",
+            &1.0f32.to_le_bytes(),
+        );
+
+        header.push(0);
+
+        header
+    }
+
+    /// Packs a run of scanlines in EXR order: for each scanline, all samples of each channel in
+    /// turn.
+    fn exr_scanlines(&self, first_row: usize, rows: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(rows * self.width * CHANNELS.len() * 4);
+
+        for row in
+            self.pixels[first_row * self.width..(first_row + rows) * self.width].chunks(self.width)
+        {
+            for (_, component) in CHANNELS {
+                for pixel in row {
+                    data.extend_from_slice(&(pixel.components()[component] as f32).to_le_bytes());
+                }
+            }
+        }
+
+        data
+    }
+}
+
+fn write_attribute(header: &mut Vec<u8>, name: &str, attribute_type: &str, value: &[u8]) {
+    header.extend_from_slice(name.as_bytes());
+    header.push(0);
+    header.extend_from_slice(attribute_type.as_bytes());
+    header.push(0);
+    header.extend_from_slice(&(value.len() as i32).to_le_bytes());
+    header.extend_from_slice(value);
+}
+
+/// Applies OpenEXR's ZIP filter (byte interleaving followed by a delta predictor) and deflates
+/// the result. Blocks that don't shrink are stored uncompressed, as the format requires.
+fn zip_compress(raw: &[u8]) -> io::Result<Vec<u8>> {
+    let mut filtered = Vec::with_capacity(raw.len());
+    filtered.extend(raw.iter().step_by(2));
+    filtered.extend(raw.iter().skip(1).step_by(2));
+
+    let mut previous = filtered.first().copied().unwrap_or(0);
+
+    for byte in filtered.iter_mut().skip(1) {
+        let current = *byte;
+        *byte = current.wrapping_sub(previous).wrapping_add(128);
+        previous = current;
+    }
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&filtered)?;
+
+    let compressed = encoder.finish()?;
+
+    Ok(if compressed.len() < raw.len() {
+        compressed
+    } else {
+        raw.to_vec()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::canvas::Canvas;
+    use crate::canvas::exr::{ExrCompression, MAGIC};
+    use crate::color::Color;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    type Attribute = (String, String, Vec<u8>);
+
+    fn gradient(width: usize, height: usize) -> Canvas {
+        let mut canvas = Canvas::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                canvas.set_pixel(x, y, Color::new(x as f64 * 1.5, y as f64, -(x as f64)));
+            }
+        }
+
+        canvas
+    }
+
+    /// Returns the header attributes and the remainder of the file following the header.
+    fn parse_header(bytes: &[u8]) -> (Vec<Attribute>, &[u8]) {
+        assert_eq!(MAGIC, bytes[0..4]);
+        assert_eq!(2, u32::from_le_bytes(bytes[4..8].try_into().unwrap()));
+
+        let mut attributes = Vec::new();
+        let mut position = 8;
+
+        let read_string = |position: &mut usize| {
+            let end = *position + bytes[*position..].iter().position(|&b| b == 0).unwrap();
+            let string = String::from_utf8(bytes[*position..end].to_vec()).unwrap();
+            *position = end + 1;
+
+            string
+        };
+
+        loop {
+            let name = read_string(&mut position);
+
+            if name.is_empty() {
+                break;
+            }
+
+            let attribute_type = read_string(&mut position);
+            let size =
+                i32::from_le_bytes(bytes[position..position + 4].try_into().unwrap()) as usize;
+
+            position += 4;
+            attributes.push((
+                name,
+                attribute_type,
+                bytes[position..position + size].to_vec(),
+            ));
+            position += size;
+        }
+
+        (attributes, &bytes[position..])
+    }
+
+    fn read_block(bytes: &[u8], offset: u64) -> (i32, &[u8]) {
+        let offset = offset as usize;
+        let y = i32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        let size = i32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()) as usize;
+
+        (y, &bytes[offset + 8..offset + 8 + size])
+    }
+
+    #[test]
+    fn test_write_uncompressed() {
+        let canvas = gradient(2, 3);
+
+        let mut bytes = Vec::new();
+        canvas
+            .write_as_exr(&mut bytes, ExrCompression::None)
+            .unwrap();
+
+        let (attributes, remainder) = parse_header(&bytes);
+
+        let compression = attributes
+            .iter()
+            .find(|(name, _, _)| name == "compression")
+            .unwrap();
+
+        assert_eq!(vec![0], compression.2);
+
+        let data_window = attributes
+            .iter()
+            .find(|(name, _, _)| name == "dataWindow")
+            .unwrap();
+
+        assert_eq!("box2i", data_window.1);
+        assert_eq!(
+            [0i32, 0, 1, 2]
+                .iter()
+                .flat_map(|bound| bound.to_le_bytes())
+                .collect::<Vec<u8>>(),
+            data_window.2
+        );
+
+        // One block per scanline
+        let offsets: Vec<u64> = remainder[..24]
+            .chunks(8)
+            .map(|offset| u64::from_le_bytes(offset.try_into().unwrap()))
+            .collect();
+
+        let (y, data) = read_block(&bytes, offsets[1]);
+        assert_eq!(1, y);
+
+        let samples: Vec<f32> = data
+            .chunks(4)
+            .map(|sample| f32::from_le_bytes(sample.try_into().unwrap()))
+            .collect();
+
+        // B, G and R channels for the two pixels in the second row
+        assert_eq!(vec![0.0, -1.0, 1.0, 1.0, 0.0, 1.5], samples);
+    }
+
+    #[test]
+    fn test_write_zip() {
+        let canvas = gradient(64, 20);
+
+        let mut bytes = Vec::new();
+        canvas
+            .write_as_exr(&mut bytes, ExrCompression::Zip)
+            .unwrap();
+
+        let (_, remainder) = parse_header(&bytes);
+
+        // Twenty scanlines fit into two sixteen-line blocks
+        let offsets: Vec<u64> = remainder[..16]
+            .chunks(8)
+            .map(|offset| u64::from_le_bytes(offset.try_into().unwrap()))
+            .collect();
+
+        let (y, data) = read_block(&bytes, offsets[0]);
+        assert_eq!(0, y);
+
+        let mut filtered = Vec::new();
+        ZlibDecoder::new(data).read_to_end(&mut filtered).unwrap();
+
+        for i in 1..filtered.len() {
+            filtered[i] = filtered[i - 1].wrapping_add(filtered[i]).wrapping_sub(128);
+        }
+
+        let (evens, odds) = filtered.split_at(filtered.len().div_ceil(2));
+        let mut raw = Vec::new();
+
+        for i in 0..filtered.len() {
+            raw.push(if i.is_multiple_of(2) {
+                evens[i / 2]
+            } else {
+                odds[i / 2]
+            });
+        }
+
+        assert_eq!(canvas.exr_scanlines(0, 16), raw);
+
+        let (y, _) = read_block(&bytes, offsets[1]);
+        assert_eq!(16, y);
+    }
+}
@@ -0,0 +1,544 @@
+pub mod exr;
+pub mod output;
+pub mod pfm;
+pub mod radiance;
+
+use crate::canvas::output::{OutputTransform, TransferFunction};
+use crate::color;
+use crate::color::Color;
+use png::{EncodingError, ScaledFloat, SrgbRenderingIntent};
+use std::fmt::{Display, Formatter};
+use std::io::{BufWriter, Write};
+use std::time::Duration;
+use std::{io, iter};
+
+// Plain PPM readers may not handle lines longer than this
+const PPM_MAX_LINE_LENGTH: usize = 70;
+
+/// The largest number of pixels (8192 × 8192) an image reader will allocate, so that a malformed
+/// header can't exhaust memory.
+const MAX_DECODED_PIXELS: usize = 1 << 26;
+
+/// A grid of pixel colors, each paired with a coverage value describing the fraction of the pixel
+/// covered by geometry. Colors are premultiplied by coverage (as they are when samples that miss
+/// all geometry contribute black), and new canvases are fully covered.
+pub struct Canvas {
+    width: usize,
+    pixels: Vec<Color>,
+    coverage: Vec<f64>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            pixels: iter::repeat_n(color::BLACK, width * height).collect(),
+            coverage: iter::repeat_n(1.0, width * height).collect(),
+        }
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        let index = self.index(x, y);
+        self.pixels[index] = color
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> &Color {
+        &self.pixels[self.index(x, y)]
+    }
+
+    pub fn set_coverage(&mut self, x: usize, y: usize, coverage: f64) {
+        let index = self.index(x, y);
+        self.coverage[index] = coverage.clamp(0.0, 1.0)
+    }
+
+    pub fn get_coverage(&self, x: usize, y: usize) -> f64 {
+        self.coverage[self.index(x, y)]
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.len() / self.width
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        if x >= self.width || y >= self.height() {
+            panic!(
+                "Position ({}, {}) out of bounds (width = {}, height = {}",
+                x,
+                y,
+                self.width,
+                self.height()
+            );
+        }
+
+        (self.width * y) + x
+    }
+
+    pub fn write_as_png<W: Write>(
+        &self,
+        writer: W,
+        options: &PngOptions,
+    ) -> Result<(), EncodingError> {
+        let writer = BufWriter::new(writer);
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height() as u32);
+
+        encoder.set_color(if options.alpha {
+            png::ColorType::Rgba
+        } else {
+            png::ColorType::Rgb
+        });
+
+        encoder.set_depth(match options.bit_depth {
+            PngBitDepth::Eight => png::BitDepth::Eight,
+            PngBitDepth::Sixteen => png::BitDepth::Sixteen,
+        });
+
+        match options.transform.transfer_function() {
+            TransferFunction::Srgb => {
+                encoder.set_source_srgb(SrgbRenderingIntent::Perceptual);
+                encoder.set_source_gamma(ScaledFloat::new(1.0 / 2.2));
+            }
+            TransferFunction::Linear => encoder.set_source_gamma(ScaledFloat::new(1.0)),
+        }
+
+        if let Some(render_time) = options.render_time {
+            encoder.add_text_chunk(
+                "Render Time".to_string(),
+                format!("{:.3} s", render_time.as_secs_f64()),
+            )?;
+        }
+
+        if let Some(samples_per_pixel) = options.samples_per_pixel {
+            encoder.add_text_chunk(
+                "Samples Per Pixel".to_string(),
+                samples_per_pixel.to_string(),
+            )?;
+        }
+
+        let mut writer = encoder.write_header()?;
+
+        writer.write_image_data(&self.to_png_samples(options))
+    }
+
+    /// Writes this canvas as a plain-text (P3) PPM image.
+    pub fn write_as_ppm<W: Write>(&self, writer: W, transform: &OutputTransform) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+
+        write!(writer, "P3\n{} {}\n255\n", self.width, self.height())?;
+
+        for row in self.pixels.chunks(self.width) {
+            let mut line = String::new();
+
+            for value in row
+                .iter()
+                .flat_map(|pixel| Self::quantize(transform, pixel))
+            {
+                let value = value.to_string();
+
+                if line.len() + 1 + value.len() > PPM_MAX_LINE_LENGTH {
+                    writeln!(writer, "{line}")?;
+                    line.clear();
+                }
+
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+
+                line.push_str(&value);
+            }
+
+            writeln!(writer, "{line}")?;
+        }
+
+        writer.flush()
+    }
+
+    fn to_png_samples(&self, options: &PngOptions) -> Vec<u8> {
+        let channels = if options.alpha { 4 } else { 3 };
+        let mut samples = Vec::new();
+
+        for (pixel, &coverage) in self.pixels.iter().zip(&self.coverage) {
+            // Without an alpha channel, pixels stay composited over black as in PPM output
+            let pixel = match options.alpha_mode {
+                AlphaMode::Straight if options.alpha && coverage > 0.0 => *pixel * (1.0 / coverage),
+                _ => *pixel,
+            };
+
+            let [r, g, b] = options.transform.apply(&pixel);
+
+            for component in [r, g, b, coverage].into_iter().take(channels) {
+                match options.bit_depth {
+                    PngBitDepth::Eight => samples.push((component * 255.0).round() as u8),
+                    PngBitDepth::Sixteen => samples
+                        .extend_from_slice(&((component * 65535.0).round() as u16).to_be_bytes()),
+                }
+            }
+        }
+
+        samples
+    }
+
+    fn quantize(transform: &OutputTransform, pixel: &Color) -> [u8; 3] {
+        transform
+            .apply(pixel)
+            .map(|component| (component * 255.0).round() as u8)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PngBitDepth {
+    Eight,
+    Sixteen,
+}
+
+/// Determines how color channels relate to the alpha channel in output with transparency.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AlphaMode {
+    /// Color channels are divided by coverage, as the PNG specification expects.
+    Straight,
+    /// Color channels are written as stored, already multiplied by coverage.
+    Premultiplied,
+}
+
+/// Controls the sample format and metadata of PNG output. By default, images are written as
+/// 8-bit, sRGB-encoded RGBA with straight alpha and no text metadata.
+#[derive(Clone, Debug)]
+pub struct PngOptions {
+    bit_depth: PngBitDepth,
+    alpha: bool,
+    alpha_mode: AlphaMode,
+    transform: OutputTransform,
+    render_time: Option<Duration>,
+    samples_per_pixel: Option<usize>,
+}
+
+impl PngOptions {
+    pub fn with_bit_depth(mut self, bit_depth: PngBitDepth) -> Self {
+        self.bit_depth = bit_depth;
+        self
+    }
+
+    /// Selects between RGBA (`true`) and RGB (`false`) output.
+    pub fn with_alpha(mut self, alpha: bool) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn with_alpha_mode(mut self, alpha_mode: AlphaMode) -> Self {
+        self.alpha_mode = alpha_mode;
+        self
+    }
+
+    pub fn with_transform(mut self, transform: OutputTransform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Records the time spent rendering the image in a text chunk.
+    pub fn with_render_time(mut self, render_time: Duration) -> Self {
+        self.render_time = Some(render_time);
+        self
+    }
+
+    /// Records the number of samples taken for each pixel in a text chunk.
+    pub fn with_samples_per_pixel(mut self, samples_per_pixel: usize) -> Self {
+        self.samples_per_pixel = Some(samples_per_pixel);
+        self
+    }
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        PngOptions {
+            bit_depth: PngBitDepth::Eight,
+            alpha: true,
+            alpha_mode: AlphaMode::Straight,
+            transform: OutputTransform::default(),
+            render_time: None,
+            samples_per_pixel: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DecodingError {
+    Io(io::Error),
+    Format(String),
+}
+
+impl Display for DecodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodingError::Io(error) => write!(f, "Failed to read image: {error}"),
+            DecodingError::Format(message) => write!(f, "Malformed image: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodingError::Io(error) => Some(error),
+            DecodingError::Format(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for DecodingError {
+    fn from(error: io::Error) -> Self {
+        DecodingError::Io(error)
+    }
+}
+
+/// Checks that the dimensions read from an image header describe a canvas small enough to
+/// allocate.
+fn check_dimensions(width: usize, height: usize) -> Result<(), DecodingError> {
+    match width.checked_mul(height) {
+        Some(0) => Err(DecodingError::Format("image has no pixels".into())),
+        Some(pixels) if pixels <= MAX_DECODED_PIXELS => Ok(()),
+        _ => Err(DecodingError::Format(format!(
+            "image is too large: {width}×{height}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::canvas::output::{OutputTransform, ToneMapping, TransferFunction};
+    use crate::canvas::{AlphaMode, Canvas, PngBitDepth, PngOptions};
+    use crate::color::Color;
+    use indoc::indoc;
+    use png::{ScaledFloat, SrgbRenderingIntent};
+    use std::time::Duration;
+
+    #[test]
+    fn test_dimensions() {
+        let width = 17;
+        let height = 19;
+
+        let canvas = Canvas::new(width, height);
+
+        assert_eq!(width, canvas.width());
+        assert_eq!(height, canvas.height());
+    }
+
+    #[test]
+    fn test_set_get_pixel() {
+        let mut canvas = Canvas::new(1, 1);
+        let red = Color::new(1.0, 0.0, 0.0);
+
+        canvas.set_pixel(0, 0, red);
+
+        assert_eq!(&red, canvas.get_pixel(0, 0));
+    }
+
+    #[test]
+    fn test_coverage() {
+        let mut canvas = Canvas::new(2, 1);
+
+        assert_eq!(1.0, canvas.get_coverage(0, 0));
+
+        canvas.set_coverage(0, 0, 0.25);
+        canvas.set_coverage(1, 0, 1.5);
+
+        assert_eq!(0.25, canvas.get_coverage(0, 0));
+        assert_eq!(1.0, canvas.get_coverage(1, 0));
+    }
+
+    #[test]
+    fn test_to_rgba() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.set_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        canvas.set_pixel(0, 1, Color::new(0.0, 0.5, 0.0));
+        canvas.set_pixel(1, 1, Color::new(-0.5, 0.0, 1.0));
+
+        #[rustfmt::skip]
+        assert_eq!(
+            vec![255, 0,   0,   255,
+                 0,   0,   0,   255,
+                 0,   128, 0,   255,
+                 0,   0,   255, 255],
+            canvas.to_png_samples(&PngOptions::default().with_transform(OutputTransform::linear())));
+    }
+
+    #[test]
+    fn test_to_rgba_srgb() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set_pixel(0, 0, Color::new(0.5, 0.0, 1.0));
+        canvas.set_pixel(1, 0, Color::new(3.0, 1.0, 0.0));
+
+        let transform = OutputTransform::new(0.0, ToneMapping::Reinhard, TransferFunction::Srgb);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            vec![156, 0,   188, 255,
+                 225, 188, 0,   255],
+            canvas.to_png_samples(&PngOptions::default().with_transform(transform)));
+    }
+
+    #[test]
+    fn test_to_rgba_alpha() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set_pixel(0, 0, Color::new(0.25, 0.5, 0.0));
+        canvas.set_coverage(0, 0, 0.5);
+        canvas.set_coverage(1, 0, 0.0);
+
+        let options = PngOptions::default().with_transform(OutputTransform::linear());
+
+        #[rustfmt::skip]
+        assert_eq!(
+            vec![128, 255, 0, 128,
+                 0,   0,   0, 0],
+            canvas.to_png_samples(&options));
+
+        #[rustfmt::skip]
+        assert_eq!(
+            vec![64, 128, 0, 128,
+                 0,  0,   0, 0],
+            canvas.to_png_samples(&options.with_alpha_mode(AlphaMode::Premultiplied)));
+    }
+
+    #[test]
+    fn test_to_rgb_coverage() {
+        let mut canvas = Canvas::new(1, 1);
+        canvas.set_pixel(0, 0, Color::new(0.25, 0.5, 0.0));
+        canvas.set_coverage(0, 0, 0.5);
+
+        let options = PngOptions::default()
+            .with_transform(OutputTransform::linear())
+            .with_alpha(false);
+
+        // Partly covered pixels aren't brightened when there's no alpha channel to dim them
+        assert_eq!(vec![64, 128, 0], canvas.to_png_samples(&options));
+    }
+
+    #[test]
+    fn test_to_png_samples() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set_pixel(0, 0, Color::new(1.0, 0.5, 0.0));
+        canvas.set_pixel(1, 0, Color::new(0.25, 2.0, 1.0));
+
+        let options = PngOptions::default()
+            .with_transform(OutputTransform::linear())
+            .with_alpha(false)
+            .with_bit_depth(PngBitDepth::Sixteen);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            vec![0xff, 0xff, 0x80, 0x00, 0x00, 0x00,
+                 0x40, 0x00, 0xff, 0xff, 0xff, 0xff],
+            canvas.to_png_samples(&options));
+    }
+
+    #[test]
+    fn test_write_as_png() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.set_pixel(2, 1, Color::new(0.5, 0.5, 0.5));
+
+        let options = PngOptions::default()
+            .with_bit_depth(PngBitDepth::Sixteen)
+            .with_alpha(false)
+            .with_render_time(Duration::from_millis(1500))
+            .with_samples_per_pixel(16);
+
+        let mut bytes = Vec::new();
+        canvas.write_as_png(&mut bytes, &options).unwrap();
+
+        let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        let info = reader.info();
+
+        assert_eq!((3, 2), info.size());
+        assert_eq!(png::ColorType::Rgb, info.color_type);
+        assert_eq!(png::BitDepth::Sixteen, info.bit_depth);
+        assert_eq!(Some(SrgbRenderingIntent::Perceptual), info.srgb);
+
+        let text: Vec<(&str, &str)> = info
+            .uncompressed_latin1_text
+            .iter()
+            .map(|chunk| (chunk.keyword.as_str(), chunk.text.as_str()))
+            .collect();
+
+        assert_eq!(
+            vec![("Render Time", "1.500 s"), ("Samples Per Pixel", "16")],
+            text
+        );
+    }
+
+    #[test]
+    fn test_write_as_png_linear() {
+        let canvas = Canvas::new(1, 1);
+
+        let mut bytes = Vec::new();
+        canvas
+            .write_as_png(
+                &mut bytes,
+                &PngOptions::default().with_transform(OutputTransform::linear()),
+            )
+            .unwrap();
+
+        let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        let info = reader.info();
+
+        assert_eq!(png::ColorType::Rgba, info.color_type);
+        assert_eq!(png::BitDepth::Eight, info.bit_depth);
+        assert_eq!(None, info.srgb);
+        assert_eq!(Some(ScaledFloat::new(1.0)), info.source_gamma);
+    }
+
+    #[test]
+    fn test_write_as_ppm() {
+        let mut canvas = Canvas::new(5, 3);
+        canvas.set_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        canvas.set_pixel(2, 1, Color::new(0.0, 0.5, 0.0));
+        canvas.set_pixel(4, 2, Color::new(-0.5, 0.0, 1.0));
+
+        let mut bytes = Vec::new();
+        canvas
+            .write_as_ppm(&mut bytes, &OutputTransform::linear())
+            .unwrap();
+
+        assert_eq!(
+            indoc! {"
+                P3
+                5 3
+                255
+                255 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+                0 0 0 0 0 0 0 128 0 0 0 0 0 0 0
+                0 0 0 0 0 0 0 0 0 0 0 0 0 0 255
+            "},
+            String::from_utf8(bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_as_ppm_long_lines() {
+        let mut canvas = Canvas::new(10, 2);
+
+        for y in 0..2 {
+            for x in 0..10 {
+                canvas.set_pixel(x, y, Color::new(1.0, 0.8, 0.6));
+            }
+        }
+
+        let mut bytes = Vec::new();
+        canvas
+            .write_as_ppm(&mut bytes, &OutputTransform::linear())
+            .unwrap();
+
+        assert_eq!(
+            indoc! {"
+                P3
+                10 2
+                255
+                255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204
+                153 255 204 153 255 204 153 255 204 153 255 204 153
+                255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204
+                153 255 204 153 255 204 153 255 204 153 255 204 153
+            "},
+            String::from_utf8(bytes).unwrap()
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use crate::color::Color;
+
+/// Compresses scene-referred (unbounded) color values into the displayable [0, 1] range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ToneMapping {
+    /// Clips each component to [0, 1] with no other adjustment.
+    Clamp,
+    /// Maps each component `c` to `c / (1 + c)`.
+    Reinhard,
+    /// Reinhard's operator, extended so that `white_point` (and anything brighter) maps to 1.
+    ExtendedReinhard { white_point: f64 },
+    /// Krzysztof Narkowicz's curve fit of the ACES filmic reference rendering transform.
+    AcesFilmic,
+}
+
+impl ToneMapping {
+    fn apply(&self, component: f64) -> f64 {
+        let component = component.max(0.0);
+
+        let mapped = match self {
+            ToneMapping::Clamp => component,
+            ToneMapping::Reinhard => component / (1.0 + component),
+            ToneMapping::ExtendedReinhard { white_point } => {
+                component * (1.0 + (component / (white_point * white_point))) / (1.0 + component)
+            }
+            ToneMapping::AcesFilmic => {
+                (component * ((2.51 * component) + 0.03))
+                    / ((component * ((2.43 * component) + 0.59)) + 0.14)
+            }
+        };
+
+        mapped.clamp(0.0, 1.0)
+    }
+}
+
+/// Encodes linear, display-referred values for storage in an integer image format.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransferFunction {
+    Linear,
+    /// The sRGB opto-electronic transfer function (roughly a 1/2.2 gamma curve).
+    Srgb,
+}
+
+impl TransferFunction {
+    fn apply(&self, component: f64) -> f64 {
+        match self {
+            TransferFunction::Linear => component,
+            TransferFunction::Srgb => {
+                if component <= 0.0031308 {
+                    component * 12.92
+                } else {
+                    (1.055 * component.powf(1.0 / 2.4)) - 0.055
+                }
+            }
+        }
+    }
+}
+
+/// Converts the linear radiance values stored in a canvas to encoded output values in [0, 1].
+/// Exposure is applied first, followed by tone mapping and finally the transfer function.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OutputTransform {
+    exposure: f64,
+    tone_mapping: ToneMapping,
+    transfer_function: TransferFunction,
+}
+
+impl OutputTransform {
+    /// Creates a new output transform. Exposure is expressed in stops; each additional stop
+    /// doubles the brightness of the image.
+    pub fn new(
+        exposure: f64,
+        tone_mapping: ToneMapping,
+        transfer_function: TransferFunction,
+    ) -> Self {
+        Self {
+            exposure,
+            tone_mapping,
+            transfer_function,
+        }
+    }
+
+    /// Returns a transform that writes clamped linear values with no encoding.
+    pub fn linear() -> Self {
+        Self::new(0.0, ToneMapping::Clamp, TransferFunction::Linear)
+    }
+
+    pub fn exposure(&self) -> f64 {
+        self.exposure
+    }
+
+    pub fn tone_mapping(&self) -> &ToneMapping {
+        &self.tone_mapping
+    }
+
+    pub fn transfer_function(&self) -> &TransferFunction {
+        &self.transfer_function
+    }
+
+    pub fn apply(&self, color: &Color) -> [f64; 3] {
+        let scale = 2.0f64.powf(self.exposure);
+
+        [0, 1, 2].map(|i| {
+            self.transfer_function
+                .apply(self.tone_mapping.apply(color.components()[i] * scale))
+        })
+    }
+}
+
+impl Default for OutputTransform {
+    fn default() -> Self {
+        Self::new(0.0, ToneMapping::Clamp, TransferFunction::Srgb)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::canvas::output::{OutputTransform, ToneMapping, TransferFunction};
+    use crate::color::Color;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+
+    #[test]
+    fn test_tone_mapping() {
+        assert_f64_near!(1.0, ToneMapping::Clamp.apply(1.5));
+        assert_f64_near!(0.0, ToneMapping::Clamp.apply(-0.5));
+
+        assert_f64_near!(0.5, ToneMapping::Reinhard.apply(1.0));
+        assert_f64_near!(0.8, ToneMapping::Reinhard.apply(4.0));
+
+        let extended = ToneMapping::ExtendedReinhard { white_point: 4.0 };
+        assert_f64_near!(1.0, extended.apply(4.0));
+        assert_f64_near!(1.0, extended.apply(16.0));
+        assert_f64_near!(0.53125, extended.apply(1.0));
+
+        assert_f64_near!(0.0, ToneMapping::AcesFilmic.apply(0.0));
+        assert_float_absolute_eq!(0.8037, ToneMapping::AcesFilmic.apply(1.0), 1e-4);
+        assert_f64_near!(1.0, ToneMapping::AcesFilmic.apply(100.0));
+    }
+
+    #[test]
+    fn test_srgb() {
+        assert_f64_near!(0.0, TransferFunction::Srgb.apply(0.0));
+        assert_f64_near!(1.0, TransferFunction::Srgb.apply(1.0));
+        assert_f64_near!(0.001292, TransferFunction::Srgb.apply(0.0001));
+        assert_float_absolute_eq!(0.7354, TransferFunction::Srgb.apply(0.5), 1e-4);
+        assert_float_absolute_eq!(0.2136, TransferFunction::Srgb.apply(0.0375), 1e-4);
+    }
+
+    #[test]
+    fn test_output_transform() {
+        let transform = OutputTransform::new(1.0, ToneMapping::Reinhard, TransferFunction::Linear);
+        let [r, g, b] = transform.apply(&Color::new(0.5, 1.5, 0.0));
+
+        assert_f64_near!(0.5, r);
+        assert_f64_near!(0.75, g);
+        assert_f64_near!(0.0, b);
+
+        let [r, g, b] = OutputTransform::linear().apply(&Color::new(0.25, 2.0, -1.0));
+
+        assert_f64_near!(0.25, r);
+        assert_f64_near!(1.0, g);
+        assert_f64_near!(0.0, b);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::canvas;
+use crate::canvas::{Canvas, DecodingError};
+use crate::color::Color;
+use std::io;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+impl Canvas {
+    /// Writes this canvas as a little-endian, three-channel Portable Float Map. Components are
+    /// stored as 32-bit floats without clamping.
+    pub fn write_as_pfm<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+
+        // A negative scale factor indicates little-endian sample data
+        write!(writer, "PF\n{} {}\n-1.0\n", self.width, self.height())?;
+
+        // PFM scanlines run from the bottom of the image to the top
+        for row in self.pixels.chunks(self.width).rev() {
+            for pixel in row {
+                for component in pixel.components() {
+                    writer.write_all(&(*component as f32).to_le_bytes())?;
+                }
+            }
+        }
+
+        writer.flush()
+    }
+
+    /// Reads a Portable Float Map in either byte order. Grayscale (`Pf`) images are expanded to
+    /// three identical channels.
+    pub fn read_pfm<R: Read>(reader: R) -> Result<Canvas, DecodingError> {
+        let mut reader = BufReader::new(reader);
+
+        let channels = match read_token(&mut reader)?.as_str() {
+            "PF" => 3,
+            "Pf" => 1,
+            magic => {
+                return Err(DecodingError::Format(format!(
+                    "unrecognized PFM signature: {magic}"
+                )));
+            }
+        };
+
+        let width: usize = parse_token(&read_token(&mut reader)?)?;
+        let height: usize = parse_token(&read_token(&mut reader)?)?;
+        let scale: f64 = parse_token(&read_token(&mut reader)?)?;
+
+        canvas::check_dimensions(width, height)?;
+
+        let little_endian = scale < 0.0;
+        let mut canvas = Canvas::new(width, height);
+        let mut sample = [0u8; 4];
+        let mut components = [0.0; 3];
+
+        for y in (0..height).rev() {
+            for x in 0..width {
+                for component in components.iter_mut().take(channels) {
+                    reader.read_exact(&mut sample)?;
+
+                    *component = if little_endian {
+                        f32::from_le_bytes(sample)
+                    } else {
+                        f32::from_be_bytes(sample)
+                    } as f64;
+                }
+
+                if channels == 1 {
+                    components = [components[0]; 3];
+                }
+
+                canvas.set_pixel(
+                    x,
+                    y,
+                    Color::new(components[0], components[1], components[2]),
+                );
+            }
+        }
+
+        Ok(canvas)
+    }
+}
+
+/// Reads a whitespace-delimited header token, consuming exactly one trailing whitespace byte so
+/// the reader is left at the start of the binary sample data after the final token.
+fn read_token<R: BufRead>(reader: &mut R) -> Result<String, DecodingError> {
+    let mut token = Vec::new();
+    let mut byte = [0u8; 1];
+
+    loop {
+        reader.read_exact(&mut byte)?;
+
+        if byte[0].is_ascii_whitespace() {
+            if !token.is_empty() {
+                break;
+            }
+        } else {
+            token.push(byte[0]);
+        }
+    }
+
+    String::from_utf8(token).map_err(|_| DecodingError::Format("non-ASCII header".into()))
+}
+
+fn parse_token<T: std::str::FromStr>(token: &str) -> Result<T, DecodingError> {
+    token
+        .parse()
+        .map_err(|_| DecodingError::Format(format!("invalid header value: {token}")))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::canvas::Canvas;
+    use crate::color::Color;
+
+    #[test]
+    fn test_write_pfm() {
+        let mut canvas = Canvas::new(1, 2);
+        canvas.set_pixel(0, 0, Color::new(1.0, 2.0, 3.0));
+        canvas.set_pixel(0, 1, Color::new(-0.5, 0.0, 100.0));
+
+        let mut bytes = Vec::new();
+        canvas.write_as_pfm(&mut bytes).unwrap();
+
+        let header = b"PF\n1 2\n-1.0\n";
+        assert_eq!(header, &bytes[..header.len()]);
+
+        let samples: Vec<f32> = bytes[header.len()..]
+            .chunks(4)
+            .map(|sample| f32::from_le_bytes(sample.try_into().unwrap()))
+            .collect();
+
+        // Bottom row first
+        assert_eq!(vec![-0.5, 0.0, 100.0, 1.0, 2.0, 3.0], samples);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.set_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        canvas.set_pixel(2, 0, Color::new(0.0, 250.0, 0.125));
+        canvas.set_pixel(1, 1, Color::new(-0.75, 0.75, 1e-6));
+
+        let mut bytes = Vec::new();
+        canvas.write_as_pfm(&mut bytes).unwrap();
+
+        let decoded = Canvas::read_pfm(bytes.as_slice()).unwrap();
+
+        assert_eq!(3, decoded.width());
+        assert_eq!(2, decoded.height());
+
+        for y in 0..2 {
+            for x in 0..3 {
+                canvas
+                    .get_pixel(x, y)
+                    .assert_approx_eq_epsilon(decoded.get_pixel(x, y), 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_big_endian_grayscale() {
+        let mut bytes = b"Pf\n2 1\n1.0\n".to_vec();
+        bytes.extend_from_slice(&0.25f32.to_be_bytes());
+        bytes.extend_from_slice(&4.0f32.to_be_bytes());
+
+        let canvas = Canvas::read_pfm(bytes.as_slice()).unwrap();
+
+        Color::new(0.25, 0.25, 0.25).assert_approx_eq(canvas.get_pixel(0, 0));
+        Color::new(4.0, 4.0, 4.0).assert_approx_eq(canvas.get_pixel(1, 0));
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        assert!(Canvas::read_pfm(b"P6\n1 1\n255\n".as_slice()).is_err());
+        assert!(Canvas::read_pfm(b"PF\n1 x\n-1.0\n".as_slice()).is_err());
+        assert!(Canvas::read_pfm(b"PF\n1 1\n-1.0\n\x00\x00".as_slice()).is_err());
+        assert!(Canvas::read_pfm(b"PF\n0 1\n-1.0\n".as_slice()).is_err());
+        assert!(Canvas::read_pfm(b"PF\n4294967296 4294967296\n-1.0\n".as_slice()).is_err());
+        assert!(Canvas::read_pfm(b"PF\n100000 100000\n-1.0\n".as_slice()).is_err());
+    }
+}
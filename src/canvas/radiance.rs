@@ -0,0 +1,336 @@
+use crate::canvas;
+use crate::canvas::{Canvas, DecodingError};
+use crate::color::Color;
+use std::io;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+const MAGIC: &str = "#?RADIANCE";
+const FORMAT: &str = "FORMAT=32-bit_rle_rgbe";
+
+// Run-length encoding only applies to scanlines in this width range; narrower or wider scanlines
+// are always stored as flat RGBE quadruples
+const MIN_RLE_WIDTH: usize = 8;
+const MAX_RLE_WIDTH: usize = 0x7fff;
+
+impl Canvas {
+    /// Writes this canvas as a Radiance HDR (RGBE) image. Each pixel is stored as an 8-bit
+    /// mantissa per channel with a shared 8-bit exponent, so values outside the [0, 1] range
+    /// survive the trip to disk.
+    pub fn write_as_hdr<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+
+        write!(writer, "{MAGIC}\n{FORMAT}\n\n")?;
+        writeln!(writer, "-Y {} +X {}", self.height(), self.width)?;
+
+        for pixel in &self.pixels {
+            writer.write_all(&to_rgbe(pixel))?;
+        }
+
+        writer.flush()
+    }
+
+    /// Reads a Radiance HDR (RGBE) image with the standard `-Y height +X width` orientation.
+    /// Both flat and run-length encoded scanlines are supported.
+    pub fn read_hdr<R: Read>(reader: R) -> Result<Canvas, DecodingError> {
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+
+        reader.read_line(&mut line)?;
+
+        if !line.trim_end().starts_with("#?") {
+            return Err(DecodingError::Format("missing Radiance signature".into()));
+        }
+
+        // Header variables run until the first blank line
+        loop {
+            line.clear();
+
+            if reader.read_line(&mut line)? == 0 {
+                return Err(DecodingError::Format("unterminated header".into()));
+            }
+
+            let line = line.trim_end();
+
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some(format) = line.strip_prefix("FORMAT=")
+                && format != "32-bit_rle_rgbe"
+            {
+                return Err(DecodingError::Format(format!(
+                    "unsupported pixel format: {format}"
+                )));
+            }
+        }
+
+        line.clear();
+        reader.read_line(&mut line)?;
+
+        let (width, height) = parse_resolution(line.trim_end())?;
+        canvas::check_dimensions(width, height)?;
+
+        let mut canvas = Canvas::new(width, height);
+        let mut scanline = vec![[0u8; 4]; width];
+
+        for y in 0..height {
+            read_scanline(&mut reader, &mut scanline)?;
+
+            for (x, rgbe) in scanline.iter().enumerate() {
+                canvas.set_pixel(x, y, from_rgbe(rgbe));
+            }
+        }
+
+        Ok(canvas)
+    }
+}
+
+fn parse_resolution(line: &str) -> Result<(usize, usize), DecodingError> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+
+    match tokens.as_slice() {
+        ["-Y", height, "+X", width] => {
+            let width = width
+                .parse()
+                .map_err(|_| DecodingError::Format(format!("invalid width: {width}")))?;
+
+            let height = height
+                .parse()
+                .map_err(|_| DecodingError::Format(format!("invalid height: {height}")))?;
+
+            Ok((width, height))
+        }
+        _ => Err(DecodingError::Format(format!(
+            "unsupported resolution string: {line}"
+        ))),
+    }
+}
+
+fn read_scanline<R: Read>(reader: &mut R, scanline: &mut [[u8; 4]]) -> Result<(), DecodingError> {
+    let width = scanline.len();
+
+    if width == 0 {
+        return Ok(());
+    }
+
+    let mut first = [0u8; 4];
+    reader.read_exact(&mut first)?;
+
+    let is_rle = (MIN_RLE_WIDTH..=MAX_RLE_WIDTH).contains(&width)
+        && first[0] == 2
+        && first[1] == 2
+        && first[2] & 0x80 == 0;
+
+    if !is_rle {
+        scanline[0] = first;
+
+        for pixel in scanline.iter_mut().skip(1) {
+            reader.read_exact(pixel)?;
+        }
+
+        return Ok(());
+    }
+
+    if ((first[2] as usize) << 8 | first[3] as usize) != width {
+        return Err(DecodingError::Format("scanline width mismatch".into()));
+    }
+
+    // Run-length encoded scanlines store each of the four components in its own run
+    for component in 0..4 {
+        let mut x = 0;
+
+        while x < width {
+            let mut count = [0u8; 1];
+            reader.read_exact(&mut count)?;
+
+            if count[0] > 128 {
+                let run = (count[0] - 128) as usize;
+
+                if x + run > width {
+                    return Err(DecodingError::Format("run overflows scanline".into()));
+                }
+
+                let mut value = [0u8; 1];
+                reader.read_exact(&mut value)?;
+
+                scanline[x..x + run]
+                    .iter_mut()
+                    .for_each(|pixel| pixel[component] = value[0]);
+
+                x += run;
+            } else {
+                let run = count[0] as usize;
+
+                if run == 0 || x + run > width {
+                    return Err(DecodingError::Format("invalid literal run".into()));
+                }
+
+                for pixel in &mut scanline[x..x + run] {
+                    let mut value = [0u8; 1];
+                    reader.read_exact(&mut value)?;
+
+                    pixel[component] = value[0];
+                }
+
+                x += run;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn to_rgbe(color: &Color) -> [u8; 4] {
+    let [r, g, b] = [
+        color.components()[0].max(0.0),
+        color.components()[1].max(0.0),
+        color.components()[2].max(0.0),
+    ];
+
+    let max = r.max(g).max(b);
+
+    if max < 1e-32 {
+        return [0, 0, 0, 0];
+    }
+
+    let (mantissa, exponent) = frexp(max);
+    let scale = mantissa * 256.0 / max;
+
+    [
+        (r * scale) as u8,
+        (g * scale) as u8,
+        (b * scale) as u8,
+        (exponent + 128) as u8,
+    ]
+}
+
+fn from_rgbe(rgbe: &[u8; 4]) -> Color {
+    if rgbe[3] == 0 {
+        return Color::new(0.0, 0.0, 0.0);
+    }
+
+    let scale = 2.0f64.powi(rgbe[3] as i32 - (128 + 8));
+
+    Color::new(
+        (rgbe[0] as f64 + 0.5) * scale,
+        (rgbe[1] as f64 + 0.5) * scale,
+        (rgbe[2] as f64 + 0.5) * scale,
+    )
+}
+
+/// Splits a positive, finite value into a mantissa in [0.5, 1) and a power-of-two exponent.
+fn frexp(value: f64) -> (f64, i32) {
+    let mut exponent = value.log2().floor() as i32 + 1;
+    let mut mantissa = value / 2.0f64.powi(exponent);
+
+    // Correct for rounding error in log2 at exact powers of two
+    if mantissa >= 1.0 {
+        mantissa /= 2.0;
+        exponent += 1;
+    } else if mantissa < 0.5 {
+        mantissa *= 2.0;
+        exponent -= 1;
+    }
+
+    (mantissa, exponent)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::canvas::Canvas;
+    use crate::canvas::radiance::{frexp, from_rgbe, to_rgbe};
+    use crate::color::Color;
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_frexp() {
+        let (mantissa, exponent) = frexp(1.0);
+        assert_f64_near!(0.5, mantissa);
+        assert_eq!(1, exponent);
+
+        let (mantissa, exponent) = frexp(0.3);
+        assert_f64_near!(0.6, mantissa);
+        assert_eq!(-1, exponent);
+
+        let (mantissa, exponent) = frexp(1000.0);
+        assert_f64_near!(1000.0 / 1024.0, mantissa);
+        assert_eq!(10, exponent);
+    }
+
+    #[test]
+    fn test_rgbe() {
+        assert_eq!([0, 0, 0, 0], to_rgbe(&Color::new(0.0, 0.0, 0.0)));
+        assert_eq!([128, 64, 0, 129], to_rgbe(&Color::new(1.0, 0.5, 0.0)));
+
+        let color = Color::new(12.5, 8.0, 10.0);
+        color.assert_approx_eq_epsilon(&from_rgbe(&to_rgbe(&color)), 1e-2);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.set_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        canvas.set_pixel(2, 0, Color::new(0.0, 250.0, 0.125));
+        canvas.set_pixel(1, 1, Color::new(0.75, 0.75, 0.75));
+
+        let mut bytes = Vec::new();
+        canvas.write_as_hdr(&mut bytes).unwrap();
+
+        let decoded = Canvas::read_hdr(bytes.as_slice()).unwrap();
+
+        assert_eq!(3, decoded.width());
+        assert_eq!(2, decoded.height());
+
+        for y in 0..2 {
+            for x in 0..3 {
+                let expected = canvas.get_pixel(x, y);
+                let actual = decoded.get_pixel(x, y);
+
+                // RGBE has eight bits of mantissa relative to the brightest channel
+                let tolerance = expected.components().iter().copied().fold(0.0, f64::max) / 128.0;
+
+                for (e, a) in expected.components().iter().zip(actual.components()) {
+                    assert!((e - a).abs() <= tolerance);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_rle() {
+        let mut bytes = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 1 +X 8\n".to_vec();
+
+        // Scanline marker followed by one run per component
+        bytes.extend_from_slice(&[2, 2, 0, 8]);
+        bytes.extend_from_slice(&[128 + 8, 128]);
+        bytes.extend_from_slice(&[4, 0, 64, 128, 255, 128 + 4, 0]);
+        bytes.extend_from_slice(&[128 + 8, 0]);
+        bytes.extend_from_slice(&[128 + 8, 129]);
+
+        let canvas = Canvas::read_hdr(bytes.as_slice()).unwrap();
+
+        assert_eq!(8, canvas.width());
+        assert_eq!(1, canvas.height());
+
+        Color::new(1.0 + 1.0 / 256.0, 1.0 / 256.0, 1.0 / 256.0)
+            .assert_approx_eq(canvas.get_pixel(0, 0));
+
+        Color::new(1.0 + 1.0 / 256.0, 0.5 + 1.0 / 256.0, 1.0 / 256.0)
+            .assert_approx_eq(canvas.get_pixel(1, 0));
+
+        Color::new(1.0 + 1.0 / 256.0, 1.0 / 256.0, 1.0 / 256.0)
+            .assert_approx_eq(canvas.get_pixel(7, 0));
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        assert!(Canvas::read_hdr(b"P6\n1 1\n255\n".as_slice()).is_err());
+        assert!(Canvas::read_hdr(b"#?RADIANCE\n\n+Y 1 +X 1\n".as_slice()).is_err());
+        assert!(Canvas::read_hdr(b"#?RADIANCE\n\n-Y 1 +X 1\n\x80".as_slice()).is_err());
+        assert!(Canvas::read_hdr(b"#?RADIANCE\n\n-Y 0 +X 1\n".as_slice()).is_err());
+        assert!(
+            Canvas::read_hdr(b"#?RADIANCE\n\n-Y 4294967296 +X 4294967296\n".as_slice()).is_err()
+        );
+        assert!(Canvas::read_hdr(b"#?RADIANCE\n\n-Y 100000 +X 100000\n".as_slice()).is_err());
+    }
+}
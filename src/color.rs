@@ -20,7 +20,7 @@ pub struct Color {
 }
 
 impl Color {
-    pub fn new(r: f64, g: f64, b: f64) -> Self {
+    pub const fn new(r: f64, g: f64, b: f64) -> Self {
         Color {
             components: [r, g, b],
         }
@@ -30,6 +30,13 @@ impl Color {
         &self.components
     }
 
+    /// Returns the relative luminance of this color using the Rec. 709 primaries.
+    pub fn luminance(&self) -> f64 {
+        (0.2126 * self.components[0])
+            + (0.7152 * self.components[1])
+            + (0.0722 * self.components[2])
+    }
+
     #[cfg(test)]
     pub fn assert_approx_eq(&self, other: &Color) {
         assert_f64_near!(self.components[0], other.components[0]);
@@ -96,6 +103,7 @@ impl Mul<Color> for Color {
 #[cfg(test)]
 mod test {
     use crate::color::Color;
+    use assert_float_eq::assert_f64_near;
 
     #[test]
     fn test_color_add() {
@@ -119,4 +127,10 @@ mod test {
         Color::new(0.9, 0.2, 0.04)
             .assert_approx_eq(&(Color::new(1.0, 0.2, 0.4) * Color::new(0.9, 1.0, 0.1)));
     }
+
+    #[test]
+    fn test_luminance() {
+        assert_f64_near!(1.0, crate::color::WHITE.luminance());
+        assert_f64_near!(0.7152, Color::new(0.0, 1.0, 0.0).luminance());
+    }
 }
@@ -0,0 +1,284 @@
+use crate::matrix::Matrix;
+use crate::quaternion::Quaternion;
+use crate::transform;
+use crate::transform::Transformation;
+use crate::vector::Vector;
+use std::fmt::{Display, Formatter};
+
+/// How far the bottom row of a matrix may stray from (0, 0, 0, 1) for it to count as affine.
+const AFFINE_EPSILON: f64 = 1e-9;
+
+/// An affine transformation split into a scale, a shear, a rotation and a translation, which are
+/// applied in that order.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Decomposition {
+    scale: [f64; 3],
+    shear: [f64; 3],
+    rotation: Quaternion,
+    translation: Vector,
+}
+
+impl Decomposition {
+    /// Creates a decomposition from its parts. The shear gives how much x moves in proportion to
+    /// y, x in proportion to z and y in proportion to z, as in `Transformation::Shear`.
+    pub fn new(
+        scale: [f64; 3],
+        shear: [f64; 3],
+        rotation: Quaternion,
+        translation: Vector,
+    ) -> Self {
+        Self {
+            scale,
+            shear,
+            rotation: rotation.normalize(),
+            translation,
+        }
+    }
+
+    pub fn scale(&self) -> [f64; 3] {
+        self.scale
+    }
+
+    pub fn shear(&self) -> [f64; 3] {
+        self.shear
+    }
+
+    pub fn rotation(&self) -> &Quaternion {
+        &self.rotation
+    }
+
+    pub fn translation(&self) -> &Vector {
+        &self.translation
+    }
+
+    /// Returns the list of transformations that rebuilds the decomposed matrix.
+    pub fn transformations(&self) -> Vec<Transformation> {
+        let [scale_x, scale_y, scale_z] = self.scale;
+        let [x_y, x_z, y_z] = self.shear;
+        let [x, y, z, _] = self.translation.components();
+
+        vec![
+            Transformation::Scale(scale_x, scale_y, scale_z),
+            Transformation::Shear(x_y, x_z, 0.0, y_z, 0.0, 0.0),
+            Transformation::Quaternion(self.rotation),
+            Transformation::Translate(x, y, z),
+        ]
+    }
+
+    /// Returns the decomposition a fraction `t` of the way from this one to `other`. The scale,
+    /// shear and translation are interpolated linearly and the rotation spherically.
+    pub fn interpolate(&self, other: &Decomposition, t: f64) -> Decomposition {
+        let lerp = |a: [f64; 3], b: [f64; 3]| [0, 1, 2].map(|i| a[i] + ((b[i] - a[i]) * t));
+
+        Self {
+            scale: lerp(self.scale, other.scale),
+            shear: lerp(self.shear, other.shear),
+            rotation: self.rotation.slerp(&other.rotation, t),
+            translation: self.translation + &((other.translation - &self.translation) * t),
+        }
+    }
+}
+
+/// Decomposes an affine matrix by orthonormalizing the columns of its upper left 3×3 block in
+/// turn (Thomas, "Decomposing a Matrix into Simple Transformations", Graphics Gems II). Matrices
+/// that mirror space come out with all three scale factors negated.
+impl TryFrom<&Matrix<4>> for Decomposition {
+    type Error = DecompositionError;
+
+    fn try_from(matrix: &Matrix<4>) -> Result<Self, Self::Error> {
+        let affine = (0..4).all(|col| {
+            let expected = if col == 3 { 1.0 } else { 0.0 };
+
+            (matrix[(3, col)] - expected).abs() < AFFINE_EPSILON
+        });
+
+        if !affine {
+            return Err(DecompositionError::NotAffine);
+        }
+
+        let column = |col| Vector::new(matrix[(0, col)], matrix[(1, col)], matrix[(2, col)]);
+        let (x_axis, y_axis, z_axis) = (column(0), column(1), column(2));
+
+        if x_axis.dot(&y_axis.cross(&z_axis)) == 0.0 {
+            return Err(DecompositionError::Singular);
+        }
+
+        let mut scale_x = x_axis.magnitude();
+        let mut x_axis = x_axis / scale_x;
+
+        let mut x_y = x_axis.dot(&y_axis);
+        let y_axis = y_axis - &(x_axis * x_y);
+        let mut scale_y = y_axis.magnitude();
+        let mut y_axis = y_axis / scale_y;
+
+        let mut x_z = x_axis.dot(&z_axis);
+        let z_axis = z_axis - &(x_axis * x_z);
+        let mut y_z = y_axis.dot(&z_axis);
+        let z_axis = z_axis - &(y_axis * y_z);
+        let mut scale_z = z_axis.magnitude();
+        let mut z_axis = z_axis / scale_z;
+
+        x_y /= scale_y;
+        x_z /= scale_z;
+        y_z /= scale_z;
+
+        // Flip every axis of a mirroring matrix, which leaves a proper rotation
+        if x_axis.dot(&y_axis.cross(&z_axis)) < 0.0 {
+            (x_axis, y_axis, z_axis) = (-x_axis, -y_axis, -z_axis);
+            (scale_x, scale_y, scale_z) = (-scale_x, -scale_y, -scale_z);
+        }
+
+        let [xx, xy, xz, _] = x_axis.components();
+        let [yx, yy, yz, _] = y_axis.components();
+        let [zx, zy, zz, _] = z_axis.components();
+
+        let rotation = Matrix::new([
+            [xx, yx, zx, 0.0],
+            [xy, yy, zy, 0.0],
+            [xz, yz, zz, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        Ok(Self {
+            scale: [scale_x, scale_y, scale_z],
+            shear: [x_y, x_z, y_z],
+            rotation: Quaternion::from(&rotation),
+            translation: Vector::new(matrix[(0, 3)], matrix[(1, 3)], matrix[(2, 3)]),
+        })
+    }
+}
+
+impl From<&Decomposition> for Matrix<4> {
+    fn from(decomposition: &Decomposition) -> Self {
+        transform::transform(&decomposition.transformations())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DecompositionError {
+    /// The matrix's bottom row isn't (0, 0, 0, 1), so it's a projection rather than an affine
+    /// transformation.
+    NotAffine,
+    /// The matrix flattens space onto a plane, line or point.
+    Singular,
+}
+
+impl Display for DecompositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecompositionError::NotAffine => write!(f, "Matrix is not affine"),
+            DecompositionError::Singular => write!(f, "Matrix is singular"),
+        }
+    }
+}
+
+impl std::error::Error for DecompositionError {}
+
+#[cfg(test)]
+mod test {
+    use crate::decomposition::{Decomposition, DecompositionError};
+    use crate::matrix::Matrix;
+    use crate::quaternion::{IDENTITY, Quaternion};
+    use crate::transform::{Transformation, transform};
+    use crate::vector::Vector;
+    use assert_float_eq::assert_float_absolute_eq;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_decompose() {
+        let rotation = Quaternion::from_axis_angle(&Vector::new(1.0, 2.0, 3.0), 0.8);
+
+        let matrix = transform(&[
+            Transformation::Scale(2.0, 3.0, 0.5),
+            Transformation::Shear(0.3, -0.2, 0.0, 0.1, 0.0, 0.0),
+            Transformation::Quaternion(rotation),
+            Transformation::Translate(1.0, -2.0, 3.0),
+        ]);
+
+        let decomposition = matrix.decompose().unwrap();
+
+        for (expected, actual) in [2.0, 3.0, 0.5].iter().zip(decomposition.scale()) {
+            assert_float_absolute_eq!(*expected, actual, 1e-12);
+        }
+
+        for (expected, actual) in [0.3, -0.2, 0.1].iter().zip(decomposition.shear()) {
+            assert_float_absolute_eq!(*expected, actual, 1e-12);
+        }
+
+        decomposition
+            .rotation()
+            .assert_approx_eq_epsilon(&rotation, 1e-12);
+
+        Vector::new(1.0, -2.0, 3.0).assert_approx_eq(decomposition.translation());
+
+        Matrix::from(&decomposition).assert_approx_eq(&matrix, 1e-12);
+    }
+
+    #[test]
+    fn test_decompose_composed() {
+        // Any chain of transformations, including ones that mirror space, rebuilds exactly
+        let matrix = transform(&[
+            Transformation::RotateX(0.4),
+            Transformation::Scale(1.0, -2.0, 1.0),
+            Transformation::Shear(0.0, 0.0, 0.5, 0.0, 0.0, 0.2),
+            Transformation::RotateZ(-1.2),
+            Transformation::Translate(4.0, 5.0, 6.0),
+        ]);
+
+        let decomposition = matrix.decompose().unwrap();
+
+        assert_float_absolute_eq!(1.0, decomposition.rotation().magnitude(), 1e-12);
+        Matrix::from(&decomposition).assert_approx_eq(&matrix, 1e-12);
+    }
+
+    #[test]
+    fn test_decompose_invalid() {
+        let projection = Matrix::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ]);
+
+        assert_eq!(
+            Some(DecompositionError::NotAffine),
+            projection.decompose().err()
+        );
+
+        let flattened = transform(&[Transformation::Scale(1.0, 0.0, 1.0)]);
+
+        assert_eq!(
+            Some(DecompositionError::Singular),
+            flattened.decompose().err()
+        );
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let start = Decomposition::new(
+            [1.0, 1.0, 1.0],
+            [0.0; 3],
+            IDENTITY,
+            Vector::new(0.0, 0.0, 0.0),
+        );
+
+        let end = Decomposition::new(
+            [3.0, 1.0, 1.0],
+            [1.0, 0.0, 0.0],
+            Quaternion::from_axis_angle(&Vector::new(0.0, 1.0, 0.0), PI / 2.0),
+            Vector::new(2.0, 0.0, 0.0),
+        );
+
+        let halfway = start.interpolate(&end, 0.5);
+
+        assert_float_absolute_eq!(2.0, halfway.scale()[0], 1e-12);
+        assert_float_absolute_eq!(0.5, halfway.shear()[0], 1e-12);
+
+        halfway.rotation().assert_approx_eq_epsilon(
+            &Quaternion::from_axis_angle(&Vector::new(0.0, 1.0, 0.0), PI / 4.0),
+            1e-12,
+        );
+
+        Vector::new(1.0, 0.0, 0.0).assert_approx_eq(halfway.translation());
+    }
+}
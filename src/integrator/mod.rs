@@ -0,0 +1,13 @@
+pub mod path;
+pub mod phong;
+
+use crate::color::Color;
+use crate::random::Random;
+use crate::ray::Ray;
+use crate::world::World;
+
+/// Computes the light arriving at the camera along a ray.
+pub trait Integrator {
+    /// Returns the radiance arriving along `ray`, or `None` if the ray doesn't hit anything.
+    fn radiance(&self, world: &World, ray: &Ray, random: &mut Random) -> Option<Color>;
+}
@@ -0,0 +1,347 @@
+use crate::color;
+use crate::color::Color;
+use crate::integrator::Integrator;
+use crate::intersection;
+use crate::material::Material;
+use crate::random::Random;
+use crate::ray::Ray;
+use crate::sampling::warp::cosine_sample_hemisphere;
+use crate::vector::{Point, Vector};
+use crate::world::{SHADOW_EPSILON, World};
+use std::f64::consts::PI;
+
+/// The largest probability with which Russian roulette lets a path continue, so that even very
+/// bright paths eventually terminate.
+const MAX_CONTINUE_PROBABILITY: f64 = 0.95;
+
+/// Estimates global illumination by following random paths of light bounces through the scene.
+///
+/// At each bounce, direct light from the world's lights and one point on its emissive shapes is
+/// sampled explicitly (next-event estimation) and the path continues in a direction chosen by
+/// importance sampling the material's BRDF (see `Material::brdf`). Since emissive shapes are
+/// sampled directly, their emission is only counted when a camera ray hits them.
+///
+/// Paths that hit translucent materials either reflect off the surface or take a random walk
+/// beneath it (see `Subsurface::walk`), with equal probability.
+///
+/// Paths passing through volumes may be scattered inside them at distances chosen in proportion to
+/// the medium's transmittance, in which case light is gathered at the scattering point and the path
+/// continues in a direction chosen by the medium's phase function. Fog absorbs light along each
+/// segment of a path and adds its own color in place of what it absorbs. Light intensities are
+/// treated as irradiance, so a white surface facing a light of intensity 1 reflects a radiance of
+/// 1/π. The world's ambient light is ignored.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PathIntegrator {
+    max_depth: usize,
+    roulette_depth: usize,
+}
+
+impl PathIntegrator {
+    /// Creates a new path integrator that follows paths for at most `max_depth` bounces and
+    /// starts terminating them randomly (Russian roulette) after `roulette_depth` bounces.
+    pub fn new(max_depth: usize, roulette_depth: usize) -> Self {
+        Self {
+            max_depth,
+            roulette_depth,
+        }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn roulette_depth(&self) -> usize {
+        self.roulette_depth
+    }
+
+    /// Returns the light arriving at `position` directly from the world's lights and emissive
+    /// shapes after reflecting toward `eye`.
+    fn direct_lighting(
+        world: &World,
+        material: &Material,
+        position: &Point,
+        normal: &Vector,
+        eye: &Vector,
+        time: f64,
+        random: &mut Random,
+    ) -> Color {
+        let over_position = *position + &(*normal * SHADOW_EPSILON);
+
+        let emitted = match world.sample_emitter(position, Some(normal), time, random) {
+            Some((direction, irradiance)) => {
+                material.brdf(normal, eye, &direction) * irradiance * direction.dot(normal)
+            }
+            None => color::BLACK,
+        };
+
+        world
+            .lights()
+            .iter()
+            .filter(|light| light.is_enabled())
+            .fold(emitted, |radiance, light| {
+                let direction = light.light().direction_from(position);
+                let cos = direction.dot(normal);
+
+                if cos <= 0.0 {
+                    return radiance;
+                }
+
+                let visibility = world.visibility(light, &over_position, time, random);
+
+                radiance
+                    + (material.brdf(normal, eye, &direction)
+                        * light.light().intensity_at(position)
+                        * (cos * visibility))
+            })
+    }
+}
+
+impl Default for PathIntegrator {
+    fn default() -> Self {
+        Self::new(16, 3)
+    }
+}
+
+impl Integrator for PathIntegrator {
+    fn radiance(&self, world: &World, ray: &Ray, random: &mut Random) -> Option<Color> {
+        let mut radiance = color::BLACK;
+        let mut throughput = color::WHITE;
+        let time = ray.time();
+        let mut ray = Ray::new(*ray.origin(), *ray.direction()).with_time(time);
+
+        for depth in 0..self.max_depth.max(1) {
+            let intersections = world.intersect(&ray);
+            let hit = intersection::hit(&intersections);
+            let distance = hit.map_or(f64::INFINITY, |hit| hit.distance());
+
+            let interaction = world.sample_volume_interaction(&ray, distance, random);
+
+            if let Some(fog) = world.fog() {
+                let transmittance =
+                    fog.transmittance(interaction.map_or(distance, |(distance, _)| distance));
+
+                radiance = radiance + (throughput * *fog.color() * (1.0 - transmittance));
+                throughput = throughput * transmittance;
+            }
+
+            let (origin, direction) = if let Some((distance, volume)) = interaction {
+                let medium = volume.medium();
+                let position = ray.position(distance);
+
+                throughput = throughput * medium.albedo();
+                radiance = radiance
+                    + (throughput
+                        * world.in_scattered(medium, &position, ray.direction(), time, random));
+
+                (position, medium.phase().sample(ray.direction(), random))
+            } else {
+                let Some(hit) = hit else {
+                    if depth == 0 && world.fog().is_none() {
+                        return None;
+                    }
+
+                    break;
+                };
+
+                let shape = hit.shape();
+                let material = shape.material();
+                let position = ray.position(hit.distance());
+
+                // Shade whichever side of the surface the ray arrived from
+                let mut normal = shape.normal_at(&position, time);
+
+                if normal.dot(ray.direction()) > 0.0 {
+                    normal = -normal;
+                }
+
+                let eye = -*ray.direction();
+
+                if depth == 0 {
+                    radiance = radiance + (throughput * *material.emission());
+                }
+
+                radiance = radiance
+                    + (throughput
+                        * Self::direct_lighting(
+                            world, material, &position, &normal, &eye, time, random,
+                        ));
+
+                // Translucent materials split paths evenly between reflecting off the surface and
+                // scattering beneath it
+                let (subsurface, split) = match material.subsurface() {
+                    Some(subsurface) => (Some(subsurface).filter(|_| random.next_f64() < 0.5), 2.0),
+                    None => (None, 1.0),
+                };
+
+                if let Some(subsurface) = subsurface {
+                    let Some((exit, exit_normal, weight)) =
+                        subsurface.walk(shape, &position, &normal, time, random)
+                    else {
+                        break;
+                    };
+
+                    // Light leaves the surface diffusely, so gather the irradiance at the exit
+                    // point as a Lambertian reflector would and continue in a cosine-weighted
+                    // direction
+                    throughput = throughput * weight * split;
+                    radiance = radiance
+                        + (throughput
+                            * world.irradiance(&exit, &exit_normal, time, random)
+                            * (1.0 / PI));
+
+                    (
+                        exit + &(exit_normal * SHADOW_EPSILON),
+                        cosine_sample_hemisphere(&exit_normal, random),
+                    )
+                } else {
+                    let Some((direction, weight)) = material.sample_brdf(&normal, &eye, random)
+                    else {
+                        break;
+                    };
+
+                    throughput = throughput * weight * split;
+
+                    (position + &(normal * SHADOW_EPSILON), direction)
+                }
+            };
+
+            if depth + 1 >= self.roulette_depth {
+                let probability = throughput
+                    .components()
+                    .iter()
+                    .copied()
+                    .fold(0.0, f64::max)
+                    .min(MAX_CONTINUE_PROBABILITY);
+
+                if random.next_f64() >= probability {
+                    break;
+                }
+
+                throughput = throughput * (1.0 / probability);
+            }
+
+            ray = Ray::new(origin, direction).with_time(time);
+        }
+
+        Some(radiance)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::integrator::Integrator;
+    use crate::integrator::path::PathIntegrator;
+    use crate::light::SceneLight;
+    use crate::light::point::PointLight;
+    use crate::material::Material;
+    use crate::random::Random;
+    use crate::ray::Ray;
+    use crate::sampling::warp::cosine_sample_hemisphere;
+    use crate::shape::sphere::Sphere;
+    use crate::transform::Transformation;
+    use crate::vector::{Point, Vector};
+    use crate::volume::Volume;
+    use crate::volume::fog::Fog;
+    use crate::volume::medium::HomogeneousMedium;
+    use crate::world::World;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_direct_lighting() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            color::WHITE,
+        )));
+
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        // A single bounce sees only direct light from a white, 90% diffuse surface
+        let radiance = PathIntegrator::new(1, 1)
+            .radiance(&world, &ray, &mut Random::new(0))
+            .unwrap();
+
+        assert_f64_near!(0.9 / PI, radiance.components()[0]);
+
+        assert!(
+            PathIntegrator::default()
+                .radiance(
+                    &world,
+                    &Ray::new(Point::new(0.0, 5.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+                    &mut Random::new(0),
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_furnace() {
+        // Inside a closed, emissive sphere with albedo a, radiance converges to e / (1 - a)
+        let mut world = World::new();
+        world.add_shape(Sphere::with_transformations(
+            &[],
+            Material::new(color::WHITE, 0.0, 0.5, 0.0, 1.0).with_emission(color::WHITE),
+        ));
+
+        let integrator = PathIntegrator::new(64, 3);
+        let mut random = Random::new(0);
+        let mut sum = color::BLACK;
+
+        for _ in 0..4_000 {
+            let direction = cosine_sample_hemisphere(&Vector::new(0.0, 1.0, 0.0), &mut random);
+
+            sum = sum
+                + integrator
+                    .radiance(
+                        &world,
+                        &Ray::new(Point::new(0.0, 0.0, 0.0), direction),
+                        &mut random,
+                    )
+                    .unwrap();
+        }
+
+        Color::new(2.0, 2.0, 2.0).assert_approx_eq_epsilon(&(sum * (1.0 / 4_000.0)), 0.02);
+    }
+
+    #[test]
+    fn test_volumes_and_fog() {
+        // An emissive backdrop seen through a purely absorbing ball of diameter 2
+        let mut world = World::new();
+        world.add_shape(Sphere::with_transformations(
+            &[Transformation::Scale(10.0, 10.0, 10.0)],
+            Material::new(color::BLACK, 0.0, 0.0, 0.0, 1.0).with_emission(color::WHITE),
+        ));
+        world.add_volume(Volume::new(
+            Sphere::default(),
+            HomogeneousMedium::new(0.25, 0.0),
+        ));
+
+        let integrator = PathIntegrator::default();
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut random = Random::new(0);
+
+        let samples = 10_000;
+        let mean = (0..samples)
+            .map(|_| {
+                integrator
+                    .radiance(&world, &ray, &mut random)
+                    .unwrap()
+                    .components()[0]
+            })
+            .sum::<f64>()
+            / samples as f64;
+
+        assert_float_absolute_eq!((-0.5f64).exp(), mean, 0.02);
+
+        // Rays that escape into fog see its color
+        let mut world = World::new();
+        world.set_fog(Some(Fog::new(Color::new(0.5, 0.5, 0.5), 0.1)));
+
+        Color::new(0.5, 0.5, 0.5)
+            .assert_approx_eq(&integrator.radiance(&world, &ray, &mut random).unwrap());
+    }
+}
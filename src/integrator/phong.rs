@@ -0,0 +1,63 @@
+use crate::color::Color;
+use crate::integrator::Integrator;
+use crate::random::Random;
+use crate::ray::Ray;
+use crate::world::World;
+
+/// Shades the first surface each ray hits with the Phong reflection model, using shadow rays
+/// for direct lighting and a constant ambient term in place of indirect lighting.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PhongIntegrator;
+
+impl Integrator for PhongIntegrator {
+    fn radiance(&self, world: &World, ray: &Ray, random: &mut Random) -> Option<Color> {
+        world.color_at(ray, random)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::integrator::Integrator;
+    use crate::integrator::phong::PhongIntegrator;
+    use crate::light::SceneLight;
+    use crate::light::point::PointLight;
+    use crate::random::Random;
+    use crate::ray::Ray;
+    use crate::shape::sphere::Sphere;
+    use crate::vector::{Point, Vector};
+    use crate::world::World;
+
+    #[test]
+    fn test_radiance() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            color::WHITE,
+        )));
+
+        let mut random = Random::new(0);
+
+        Color::new(1.9, 1.9, 1.9).assert_approx_eq(
+            &PhongIntegrator
+                .radiance(
+                    &world,
+                    &Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+                    &mut random,
+                )
+                .unwrap(),
+        );
+
+        assert!(
+            PhongIntegrator
+                .radiance(
+                    &world,
+                    &Ray::new(Point::new(0.0, 5.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+                    &mut random,
+                )
+                .is_none()
+        );
+    }
+}
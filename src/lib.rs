@@ -1,10 +1,23 @@
+pub mod affine;
+pub mod animation;
+pub mod camera;
 pub mod canvas;
 pub mod color;
+pub mod decomposition;
+pub mod integrator;
 pub mod intersection;
 pub mod light;
 pub mod material;
 pub mod matrix;
+pub mod projection;
+pub mod quaternion;
+pub mod random;
 pub mod ray;
+pub mod sampling;
 pub mod shape;
+mod simd;
+pub mod stereo;
 pub mod transform;
 pub mod vector;
+pub mod volume;
+pub mod world;
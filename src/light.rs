@@ -1,24 +0,0 @@
-use crate::color::Color;
-use crate::vector::Point;
-
-pub struct PointLight {
-    position: Point,
-    intensity: Color,
-}
-
-impl PointLight {
-    pub fn new(position: Point, intensity: Color) -> Self {
-        Self {
-            position,
-            intensity,
-        }
-    }
-
-    pub fn position(&self) -> &Point {
-        &self.position
-    }
-
-    pub fn intensity(&self) -> &Color {
-        &self.intensity
-    }
-}
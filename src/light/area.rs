@@ -0,0 +1,234 @@
+use crate::color::Color;
+use crate::light::Light;
+use crate::light::attenuation::Attenuation;
+use crate::light::power::Power;
+use crate::random::Random;
+use crate::sampling::pattern::SamplePattern;
+use crate::vector::{Point, Vector};
+
+/// The emitting surface of an area light.
+#[derive(Copy, Clone)]
+pub enum AreaLightShape {
+    /// A parallelogram with one corner at `corner` and edges `u` and `v`.
+    Rectangle {
+        corner: Point,
+        u: Vector,
+        v: Vector,
+    },
+    Sphere {
+        center: Point,
+        radius: f64,
+    },
+}
+
+/// A light with a finite extent, which casts soft-edged shadows. Visibility is estimated by
+/// testing shadow rays toward jittered points on the light's surface.
+pub struct AreaLight {
+    shape: AreaLightShape,
+    intensity: Color,
+    samples: usize,
+    attenuation: Attenuation,
+}
+
+impl AreaLight {
+    /// Creates a new area light. Sample points are jittered within a regular grid on the light's
+    /// surface, so `samples` is rounded up to the next perfect square.
+    pub fn new(shape: AreaLightShape, intensity: Color, samples: usize) -> Self {
+        Self {
+            shape,
+            intensity,
+            samples,
+            attenuation: Attenuation::none(),
+        }
+    }
+
+    /// Creates a physically-based area light that emits the given total power, tinted by `color`,
+    /// with inverse-square falloff from its center.
+    pub fn with_power(shape: AreaLightShape, color: Color, power: Power, samples: usize) -> Self {
+        Self::new(shape, color * power.isotropic_intensity(), samples)
+            .with_attenuation(Attenuation::inverse_square())
+    }
+
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        self
+    }
+
+    pub fn rectangle(
+        corner: Point,
+        u: Vector,
+        v: Vector,
+        intensity: Color,
+        samples: usize,
+    ) -> Self {
+        Self::new(
+            AreaLightShape::Rectangle { corner, u, v },
+            intensity,
+            samples,
+        )
+    }
+
+    pub fn sphere(center: Point, radius: f64, intensity: Color, samples: usize) -> Self {
+        Self::new(
+            AreaLightShape::Sphere { center, radius },
+            intensity,
+            samples,
+        )
+    }
+
+    pub fn shape(&self) -> &AreaLightShape {
+        &self.shape
+    }
+
+    pub fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    pub fn attenuation(&self) -> &Attenuation {
+        &self.attenuation
+    }
+
+    pub fn samples(&self) -> usize {
+        SamplePattern::Jittered.sample_count(self.samples)
+    }
+
+    /// Returns the center of this light.
+    pub fn position(&self) -> Point {
+        match self.shape {
+            AreaLightShape::Rectangle { corner, u, v } => corner + &((u + &v) * 0.5),
+            AreaLightShape::Sphere { center, .. } => center,
+        }
+    }
+}
+
+impl Light for AreaLight {
+    /// Returns the direction toward the center of this light, which stands in for the whole light
+    /// when computing diffuse and specular shading.
+    fn direction_from(&self, point: &Point) -> Vector {
+        (self.position() - point).normalize()
+    }
+
+    fn distance_from(&self, point: &Point) -> f64 {
+        (self.position() - point).magnitude()
+    }
+
+    fn intensity_at(&self, point: &Point) -> Color {
+        self.intensity * self.attenuation.factor(self.distance_from(point))
+    }
+
+    /// Returns a set of points distributed over the surface of this light.
+    fn sample_points(&self, random: &mut Random) -> Vec<Point> {
+        SamplePattern::Jittered
+            .offsets(self.samples, random)
+            .into_iter()
+            .map(|(s, t)| match self.shape {
+                AreaLightShape::Rectangle { corner, u, v } => corner + &((u * s) + &(v * t)),
+                AreaLightShape::Sphere { center, radius } => {
+                    // Map the unit square to the sphere's surface with equal area per cell
+                    let z = 1.0 - (2.0 * s);
+                    let r = (1.0 - (z * z)).sqrt();
+                    let (sin, cos) = (2.0 * std::f64::consts::PI * t).sin_cos();
+
+                    center + &(Vector::new(r * cos, r * sin, z) * radius)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::light::Light;
+    use crate::light::area::AreaLight;
+    use crate::random::Random;
+    use crate::shape::sphere::Sphere;
+    use crate::vector::{Point, Vector};
+    use crate::world::World;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+
+        world
+    }
+
+    #[test]
+    fn test_rectangle_sample_points() {
+        let light = AreaLight::rectangle(
+            Point::new(-1.0, 2.0, 0.0),
+            Vector::new(2.0, 0.0, 0.0),
+            Vector::new(0.0, 0.0, 4.0),
+            color::WHITE,
+            8,
+        );
+
+        let points = light.sample_points(&mut Random::new(0));
+
+        assert_eq!(9, light.samples());
+        assert_eq!(9, points.len());
+
+        // Each point should land in its own cell of a 3x3 grid across the rectangle
+        for (i, point) in points.iter().enumerate() {
+            let (x, y, z) = point.coordinates();
+
+            assert_f64_near!(2.0, y);
+            assert_eq!(i % 3, ((x + 1.0) * 1.5) as usize);
+            assert_eq!(i / 3, (z * 0.75) as usize);
+        }
+
+        Point::new(0.0, 2.0, 2.0).assert_approx_eq(&light.position());
+    }
+
+    #[test]
+    fn test_sphere_sample_points() {
+        let light = AreaLight::sphere(Point::new(1.0, 2.0, 3.0), 0.5, color::WHITE, 16);
+
+        for point in light.sample_points(&mut Random::new(0)) {
+            assert_float_absolute_eq!(
+                0.5,
+                (&point - &Point::new(1.0, 2.0, 3.0)).magnitude(),
+                1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_area_light_visibility() {
+        let world = world();
+        let mut random = Random::new(0);
+
+        let light = AreaLight::rectangle(
+            Point::new(-0.5, -0.5, -10.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            color::WHITE,
+            16,
+        );
+
+        // Fully lit, fully shadowed, and partially shadowed (a point in the penumbra)
+        assert_f64_near!(
+            1.0,
+            light.visibility(&world, &Point::new(0.0, 0.0, -2.0), 0.0, &mut random)
+        );
+        assert_f64_near!(
+            0.0,
+            light.visibility(&world, &Point::new(0.0, 0.0, 2.0), 0.0, &mut random)
+        );
+
+        let wide = AreaLight::rectangle(
+            Point::new(-5.0, -0.5, -10.0),
+            Vector::new(10.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            color::WHITE,
+            16,
+        );
+
+        // The sphere blocks the middle two columns of sample points but not the outer two
+        assert_f64_near!(
+            0.5,
+            wide.visibility(&world, &Point::new(0.0, 0.0, 10.0), 0.0, &mut random)
+        );
+    }
+}
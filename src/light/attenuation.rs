@@ -0,0 +1,72 @@
+/// Describes how a light's intensity falls off with distance, as
+/// `1 / (constant + linear × d + quadratic × d²)`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Attenuation {
+    constant: f64,
+    linear: f64,
+    quadratic: f64,
+}
+
+impl Attenuation {
+    pub fn new(constant: f64, linear: f64, quadratic: f64) -> Self {
+        Self {
+            constant,
+            linear,
+            quadratic,
+        }
+    }
+
+    /// No falloff; intensity is the same at any distance.
+    pub fn none() -> Self {
+        Self::new(1.0, 0.0, 0.0)
+    }
+
+    /// Physically-correct falloff for a point source.
+    pub fn inverse_square() -> Self {
+        Self::new(0.0, 0.0, 1.0)
+    }
+
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+
+    pub fn linear(&self) -> f64 {
+        self.linear
+    }
+
+    pub fn quadratic(&self) -> f64 {
+        self.quadratic
+    }
+
+    /// Returns the factor by which intensity is scaled at `distance` from a light.
+    pub fn factor(&self, distance: f64) -> f64 {
+        let denominator =
+            self.constant + (self.linear * distance) + (self.quadratic * distance * distance);
+
+        if denominator > 0.0 {
+            1.0 / denominator
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl Default for Attenuation {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::light::attenuation::Attenuation;
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_factor() {
+        assert_f64_near!(1.0, Attenuation::none().factor(10.0));
+        assert_f64_near!(0.25, Attenuation::inverse_square().factor(2.0));
+        assert_f64_near!(1.0 / 7.0, Attenuation::new(1.0, 0.5, 0.25).factor(4.0));
+        assert_eq!(f64::INFINITY, Attenuation::inverse_square().factor(0.0));
+    }
+}
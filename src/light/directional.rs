@@ -0,0 +1,97 @@
+use crate::color::Color;
+use crate::light::Light;
+use crate::random::Random;
+use crate::vector::{Point, Vector};
+use crate::world::World;
+
+/// A light infinitely far away (like the sun) whose rays all travel in the same direction.
+pub struct DirectionalLight {
+    direction: Vector,
+    intensity: Color,
+}
+
+impl DirectionalLight {
+    /// Creates a new directional light whose rays travel along `direction`.
+    pub fn new(direction: Vector, intensity: Color) -> Self {
+        Self {
+            direction: direction.normalize(),
+            intensity,
+        }
+    }
+
+    pub fn direction(&self) -> &Vector {
+        &self.direction
+    }
+
+    pub fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+}
+
+impl Light for DirectionalLight {
+    fn direction_from(&self, _point: &Point) -> Vector {
+        -self.direction
+    }
+
+    fn distance_from(&self, _point: &Point) -> f64 {
+        f64::INFINITY
+    }
+
+    fn intensity_at(&self, _point: &Point) -> Color {
+        self.intensity
+    }
+
+    fn sample_points(&self, _random: &mut Random) -> Vec<Point> {
+        Vec::new()
+    }
+
+    fn visibility(&self, world: &World, point: &Point, time: f64, _random: &mut Random) -> f64 {
+        if world.is_occluded(point, &-self.direction, f64::INFINITY, time) {
+            0.0
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::light::Light;
+    use crate::light::directional::DirectionalLight;
+    use crate::random::Random;
+    use crate::shape::sphere::Sphere;
+    use crate::vector::{Point, Vector};
+    use crate::world::World;
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_direction() {
+        let light = DirectionalLight::new(Vector::new(0.0, -2.0, 0.0), color::WHITE);
+
+        Vector::new(0.0, 1.0, 0.0)
+            .assert_approx_eq(&light.direction_from(&Point::new(5.0, 0.0, 3.0)));
+        assert_eq!(
+            f64::INFINITY,
+            light.distance_from(&Point::new(5.0, 0.0, 3.0))
+        );
+    }
+
+    #[test]
+    fn test_visibility() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+
+        let light = DirectionalLight::new(Vector::new(0.0, -1.0, 0.0), color::WHITE);
+        let mut random = Random::new(0);
+
+        assert_f64_near!(
+            0.0,
+            light.visibility(&world, &Point::new(0.0, -100.0, 0.0), 0.0, &mut random)
+        );
+        assert_f64_near!(
+            1.0,
+            light.visibility(&world, &Point::new(2.0, -100.0, 0.0), 0.0, &mut random)
+        );
+    }
+}
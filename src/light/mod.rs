@@ -0,0 +1,92 @@
+pub mod area;
+pub mod attenuation;
+pub mod directional;
+pub mod point;
+pub mod power;
+pub mod spot;
+
+use crate::color::Color;
+use crate::random::Random;
+use crate::vector::{Point, Vector};
+use crate::world::World;
+
+pub trait Light {
+    /// Returns a unit vector pointing from `point` toward this light.
+    fn direction_from(&self, point: &Point) -> Vector;
+
+    /// Returns the distance from `point` to this light, which may be infinite.
+    fn distance_from(&self, point: &Point) -> f64;
+
+    /// Returns the intensity of the light arriving at `point`, including any falloff with
+    /// distance but ignoring shadows.
+    fn intensity_at(&self, point: &Point) -> Color;
+
+    /// Returns points on this light toward which shadow rays should be cast. Lights without a
+    /// position (directional lights, for example) return no points.
+    fn sample_points(&self, random: &mut Random) -> Vec<Point>;
+
+    /// Returns the fraction of this light that is visible from `point` at the given time. By
+    /// default, this is the fraction of this light's sample points that aren't shadowed.
+    fn visibility(&self, world: &World, point: &Point, time: f64, random: &mut Random) -> f64 {
+        let points = self.sample_points(random);
+
+        if points.is_empty() {
+            return 1.0;
+        }
+
+        let visible = points
+            .iter()
+            .filter(|light_point| !world.is_shadowed(point, light_point, time))
+            .count();
+
+        visible as f64 / points.len() as f64
+    }
+}
+
+/// A light placed in a world, along with flags controlling how it takes part in shading.
+pub struct SceneLight {
+    light: Box<dyn Light>,
+    enabled: bool,
+    casts_shadows: bool,
+}
+
+impl SceneLight {
+    /// Creates an enabled, shadow-casting scene light.
+    pub fn new<L: Light + 'static>(light: L) -> Self {
+        Self {
+            light: Box::new(light),
+            enabled: true,
+            casts_shadows: true,
+        }
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn with_shadows(mut self, casts_shadows: bool) -> Self {
+        self.casts_shadows = casts_shadows;
+        self
+    }
+
+    pub fn light(&self) -> &dyn Light {
+        self.light.as_ref()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn casts_shadows(&self) -> bool {
+        self.casts_shadows
+    }
+
+    pub fn set_casts_shadows(&mut self, casts_shadows: bool) {
+        self.casts_shadows = casts_shadows;
+    }
+}
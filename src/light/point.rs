@@ -0,0 +1,133 @@
+use crate::color::Color;
+use crate::light::Light;
+use crate::light::attenuation::Attenuation;
+use crate::light::power::Power;
+use crate::random::Random;
+use crate::vector::{Point, Vector};
+
+pub struct PointLight {
+    position: Point,
+    intensity: Color,
+    attenuation: Attenuation,
+}
+
+impl PointLight {
+    pub fn new(position: Point, intensity: Color) -> Self {
+        Self {
+            position,
+            intensity,
+            attenuation: Attenuation::none(),
+        }
+    }
+
+    /// Creates a physically-based light that emits the given total power, tinted by `color`,
+    /// with inverse-square falloff.
+    pub fn with_power(position: Point, color: Color, power: Power) -> Self {
+        Self::new(position, color * power.isotropic_intensity())
+            .with_attenuation(Attenuation::inverse_square())
+    }
+
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        self
+    }
+
+    pub fn position(&self) -> &Point {
+        &self.position
+    }
+
+    pub fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    pub fn attenuation(&self) -> &Attenuation {
+        &self.attenuation
+    }
+}
+
+impl Light for PointLight {
+    fn direction_from(&self, point: &Point) -> Vector {
+        (self.position - point).normalize()
+    }
+
+    fn distance_from(&self, point: &Point) -> f64 {
+        (self.position - point).magnitude()
+    }
+
+    fn intensity_at(&self, point: &Point) -> Color {
+        self.intensity * self.attenuation.factor(self.distance_from(point))
+    }
+
+    fn sample_points(&self, _random: &mut Random) -> Vec<Point> {
+        vec![self.position]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::light::Light;
+    use crate::light::attenuation::Attenuation;
+    use crate::light::point::PointLight;
+    use crate::light::power::Power;
+    use crate::random::Random;
+    use crate::shape::sphere::Sphere;
+    use crate::vector::{Point, Vector};
+    use crate::world::World;
+    use assert_float_eq::assert_f64_near;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_direction_and_distance() {
+        let light = PointLight::new(Point::new(0.0, 3.0, 4.0), color::WHITE);
+
+        Vector::new(0.0, 0.6, 0.8)
+            .assert_approx_eq(&light.direction_from(&Point::new(0.0, 0.0, 0.0)));
+        assert_f64_near!(5.0, light.distance_from(&Point::new(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_attenuation() {
+        let light = PointLight::new(Point::new(0.0, 0.0, 0.0), color::WHITE)
+            .with_attenuation(Attenuation::inverse_square());
+
+        Color::new(0.25, 0.25, 0.25)
+            .assert_approx_eq(&light.intensity_at(&Point::new(0.0, 2.0, 0.0)));
+        Color::new(0.04, 0.04, 0.04)
+            .assert_approx_eq(&light.intensity_at(&Point::new(0.0, 0.0, 5.0)));
+
+        // Lights don't fall off unless asked to
+        let light = PointLight::new(Point::new(0.0, 0.0, 0.0), color::WHITE);
+        color::WHITE.assert_approx_eq(&light.intensity_at(&Point::new(0.0, 0.0, 5.0)));
+    }
+
+    #[test]
+    fn test_with_power() {
+        let light = PointLight::with_power(
+            Point::new(0.0, 0.0, 0.0),
+            Color::new(1.0, 0.5, 0.0),
+            Power::Watts(16.0 * PI),
+        );
+
+        Color::new(1.0, 0.5, 0.0).assert_approx_eq(&light.intensity_at(&Point::new(2.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_visibility() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), color::WHITE);
+        let mut random = Random::new(0);
+
+        assert_f64_near!(
+            1.0,
+            light.visibility(&world, &Point::new(0.0, 0.0, -2.0), 0.0, &mut random)
+        );
+        assert_f64_near!(
+            0.0,
+            light.visibility(&world, &Point::new(0.0, 0.0, 2.0), 0.0, &mut random)
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use std::f64::consts::PI;
+
+/// The luminous efficacy of monochromatic 555 nm light, which relates photometric units to
+/// radiometric ones.
+pub const LUMENS_PER_WATT: f64 = 683.0;
+
+/// The total power emitted by a light, in radiometric or photometric units.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Power {
+    Watts(f64),
+    Lumens(f64),
+}
+
+impl Power {
+    pub fn watts(&self) -> f64 {
+        match self {
+            Power::Watts(watts) => *watts,
+            Power::Lumens(lumens) => lumens / LUMENS_PER_WATT,
+        }
+    }
+
+    /// Returns the radiant intensity (power per steradian) of a light that emits this power
+    /// uniformly in all directions.
+    pub fn isotropic_intensity(&self) -> f64 {
+        self.watts() / (4.0 * PI)
+    }
+
+    /// Returns the radiant intensity of a light that emits this power uniformly within a cone
+    /// with the given half-angle.
+    pub fn cone_intensity(&self, half_angle: f64) -> f64 {
+        self.watts() / (2.0 * PI * (1.0 - half_angle.cos()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::light::power::Power;
+    use assert_float_eq::assert_f64_near;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_watts() {
+        assert_f64_near!(60.0, Power::Watts(60.0).watts());
+        assert_f64_near!(2.0, Power::Lumens(1366.0).watts());
+    }
+
+    #[test]
+    fn test_intensity() {
+        assert_f64_near!(1.0, Power::Watts(4.0 * PI).isotropic_intensity());
+
+        // A cone with a half-angle of 90° covers a hemisphere
+        assert_f64_near!(1.0, Power::Watts(2.0 * PI).cone_intensity(PI / 2.0));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::color::Color;
+use crate::light::Light;
+use crate::light::attenuation::Attenuation;
+use crate::light::power::Power;
+use crate::random::Random;
+use crate::vector::{Point, Vector};
+
+/// A point light that only shines within a cone. Intensity is full within `inner_angle` of the
+/// cone's axis and falls off smoothly to zero at `outer_angle`.
+pub struct SpotLight {
+    position: Point,
+    direction: Vector,
+    inner_angle: f64,
+    outer_angle: f64,
+    intensity: Color,
+    attenuation: Attenuation,
+}
+
+impl SpotLight {
+    /// Creates a new spot light pointing along `direction`. Angles are measured in radians from
+    /// the cone's axis.
+    pub fn new(
+        position: Point,
+        direction: Vector,
+        inner_angle: f64,
+        outer_angle: f64,
+        intensity: Color,
+    ) -> Self {
+        Self {
+            position,
+            direction: direction.normalize(),
+            inner_angle,
+            outer_angle: outer_angle.max(inner_angle),
+            intensity,
+            attenuation: Attenuation::none(),
+        }
+    }
+
+    /// Creates a physically-based spot light that emits the given total power, tinted by `color`,
+    /// with inverse-square falloff. Power is spread over a cone halfway between the inner and
+    /// outer angles.
+    pub fn with_power(
+        position: Point,
+        direction: Vector,
+        inner_angle: f64,
+        outer_angle: f64,
+        color: Color,
+        power: Power,
+    ) -> Self {
+        let intensity = power.cone_intensity((inner_angle + outer_angle.max(inner_angle)) / 2.0);
+
+        Self::new(
+            position,
+            direction,
+            inner_angle,
+            outer_angle,
+            color * intensity,
+        )
+        .with_attenuation(Attenuation::inverse_square())
+    }
+
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        self
+    }
+
+    pub fn position(&self) -> &Point {
+        &self.position
+    }
+
+    pub fn direction(&self) -> &Vector {
+        &self.direction
+    }
+
+    pub fn inner_angle(&self) -> f64 {
+        self.inner_angle
+    }
+
+    pub fn outer_angle(&self) -> f64 {
+        self.outer_angle
+    }
+
+    pub fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    pub fn attenuation(&self) -> &Attenuation {
+        &self.attenuation
+    }
+
+    fn falloff(&self, point: &Point) -> f64 {
+        let cos = (point - &self.position).normalize().dot(&self.direction);
+        let (cos_inner, cos_outer) = (self.inner_angle.cos(), self.outer_angle.cos());
+
+        if cos >= cos_inner {
+            1.0
+        } else if cos <= cos_outer {
+            0.0
+        } else {
+            let t = (cos - cos_outer) / (cos_inner - cos_outer);
+            t * t * (3.0 - (2.0 * t))
+        }
+    }
+}
+
+impl Light for SpotLight {
+    fn direction_from(&self, point: &Point) -> Vector {
+        (self.position - point).normalize()
+    }
+
+    fn distance_from(&self, point: &Point) -> f64 {
+        (self.position - point).magnitude()
+    }
+
+    fn intensity_at(&self, point: &Point) -> Color {
+        self.intensity * self.falloff(point) * self.attenuation.factor(self.distance_from(point))
+    }
+
+    fn sample_points(&self, _random: &mut Random) -> Vec<Point> {
+        vec![self.position]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::light::Light;
+    use crate::light::attenuation::Attenuation;
+    use crate::light::spot::SpotLight;
+    use crate::vector::{Point, Vector};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_intensity_at() {
+        let light = SpotLight::new(
+            Point::new(0.0, 10.0, 0.0),
+            Vector::new(0.0, -1.0, 0.0),
+            PI / 8.0,
+            PI / 4.0,
+            color::WHITE,
+        );
+
+        // Inside the inner cone, halfway through the falloff, and outside the outer cone
+        color::WHITE.assert_approx_eq(&light.intensity_at(&Point::new(1.0, 0.0, 0.0)));
+
+        let halfway = ((PI / 8.0).cos() + (PI / 4.0).cos()) / 2.0;
+        let x = 10.0 * (1.0 - (halfway * halfway)).sqrt() / halfway;
+
+        Color::new(0.5, 0.5, 0.5).assert_approx_eq(&light.intensity_at(&Point::new(x, 0.0, 0.0)));
+        color::BLACK.assert_approx_eq(&light.intensity_at(&Point::new(10.0, 0.0, 0.0)));
+        color::BLACK.assert_approx_eq(&light.intensity_at(&Point::new(0.0, 20.0, 0.0)));
+    }
+
+    #[test]
+    fn test_attenuation() {
+        let light = SpotLight::new(
+            Point::new(0.0, 10.0, 0.0),
+            Vector::new(0.0, -1.0, 0.0),
+            PI / 8.0,
+            PI / 4.0,
+            color::WHITE,
+        )
+        .with_attenuation(Attenuation::new(1.0, 0.0, 0.01));
+
+        Color::new(0.5, 0.5, 0.5).assert_approx_eq(&light.intensity_at(&Point::new(0.0, 0.0, 0.0)));
+    }
+}
@@ -0,0 +1,298 @@
+use crate::color;
+use crate::color::Color;
+use crate::random::Random;
+use crate::sampling::warp::{cosine_sample_hemisphere, orthonormal_basis};
+use crate::vector::Vector;
+use std::f64::consts::PI;
+
+/// The smallest GGX alpha used, since a perfectly smooth surface has a singular distribution.
+const MIN_ALPHA: f64 = 1e-3;
+
+/// A physically-based material made of microscopic mirror-like facets (Cook-Torrance), with a
+/// GGX distribution of facet normals, the Smith masking-shadowing term and Schlick's Fresnel
+/// approximation. Dielectrics (`metallic` = 0) add a Lambertian diffuse lobe under their specular
+/// coating; metals (`metallic` = 1) tint their reflections with the base color.
+///
+/// All directions point away from the surface.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Microfacet {
+    base_color: Color,
+    metallic: f64,
+    roughness: f64,
+    ior: f64,
+}
+
+impl Microfacet {
+    pub fn new(base_color: Color, metallic: f64, roughness: f64, ior: f64) -> Self {
+        Self {
+            base_color,
+            metallic: metallic.clamp(0.0, 1.0),
+            roughness: roughness.clamp(0.0, 1.0),
+            ior,
+        }
+    }
+
+    pub fn base_color(&self) -> &Color {
+        &self.base_color
+    }
+
+    pub fn metallic(&self) -> f64 {
+        self.metallic
+    }
+
+    pub fn roughness(&self) -> f64 {
+        self.roughness
+    }
+
+    pub fn ior(&self) -> f64 {
+        self.ior
+    }
+
+    /// Returns the value of the BRDF for light arriving from `light` and leaving toward `eye`.
+    pub fn evaluate(&self, normal: &Vector, eye: &Vector, light: &Vector) -> Color {
+        let (n_dot_v, n_dot_l) = (normal.dot(eye), normal.dot(light));
+
+        if n_dot_v <= 0.0 || n_dot_l <= 0.0 {
+            return color::BLACK;
+        }
+
+        let half = (*eye + light).normalize();
+        let alpha = self.alpha();
+
+        let fresnel = self.fresnel(eye.dot(&half));
+        let distribution = ggx_distribution(normal.dot(&half), alpha);
+        let geometry = smith_g1(n_dot_v, alpha) * smith_g1(n_dot_l, alpha);
+
+        let specular = fresnel * (distribution * geometry / (4.0 * n_dot_v * n_dot_l));
+        let diffuse = (color::WHITE - fresnel) * self.base_color * ((1.0 - self.metallic) / PI);
+
+        diffuse + specular
+    }
+
+    /// Returns the probability density with which `sample` chooses `light` given `eye`.
+    pub fn pdf(&self, normal: &Vector, eye: &Vector, light: &Vector) -> f64 {
+        let (n_dot_v, n_dot_l) = (normal.dot(eye), normal.dot(light));
+
+        if n_dot_v <= 0.0 || n_dot_l <= 0.0 {
+            return 0.0;
+        }
+
+        let half = (*eye + light).normalize();
+        let n_dot_h = normal.dot(&half);
+
+        let specular_pdf =
+            ggx_distribution(n_dot_h, self.alpha()) * n_dot_h / (4.0 * eye.dot(&half));
+        let diffuse_pdf = n_dot_l / PI;
+
+        let probability = self.specular_probability();
+
+        (probability * specular_pdf) + ((1.0 - probability) * diffuse_pdf)
+    }
+
+    /// Chooses a direction from which to gather light toward `eye`, importance sampling either
+    /// the GGX distribution of normals or the diffuse lobe. Returns the direction along with
+    /// the BRDF times the cosine term divided by the sample's probability density, or `None` if
+    /// the sampled direction is below the surface.
+    pub fn sample(
+        &self,
+        normal: &Vector,
+        eye: &Vector,
+        random: &mut Random,
+    ) -> Option<(Vector, Color)> {
+        let light = if random.next_f64() < self.specular_probability() {
+            let half = sample_ggx_normal(normal, self.alpha(), random);
+            (-*eye).reflect(&half)
+        } else {
+            cosine_sample_hemisphere(normal, random)
+        };
+
+        let pdf = self.pdf(normal, eye, &light);
+
+        if pdf <= 0.0 {
+            return None;
+        }
+
+        Some((
+            light,
+            self.evaluate(normal, eye, &light) * (normal.dot(&light) / pdf),
+        ))
+    }
+
+    fn alpha(&self) -> f64 {
+        (self.roughness * self.roughness).max(MIN_ALPHA)
+    }
+
+    /// Returns the reflectance at normal incidence, which is achromatic for dielectrics and the
+    /// base color for metals.
+    fn f0(&self) -> Color {
+        let dielectric = ((self.ior - 1.0) / (self.ior + 1.0)).powi(2);
+
+        (color::WHITE * (dielectric * (1.0 - self.metallic))) + (self.base_color * self.metallic)
+    }
+
+    fn fresnel(&self, cos: f64) -> Color {
+        let f0 = self.f0();
+
+        f0 + ((color::WHITE - f0) * (1.0 - cos.clamp(0.0, 1.0)).powi(5))
+    }
+
+    fn specular_probability(&self) -> f64 {
+        0.5 + (0.5 * self.metallic)
+    }
+}
+
+impl Default for Microfacet {
+    /// A white, moderately rough dielectric with the index of refraction of glass.
+    fn default() -> Self {
+        Self::new(color::WHITE, 0.0, 0.5, 1.5)
+    }
+}
+
+/// The GGX (Trowbridge-Reitz) distribution of microfacet normals.
+fn ggx_distribution(n_dot_h: f64, alpha: f64) -> f64 {
+    if n_dot_h <= 0.0 {
+        return 0.0;
+    }
+
+    let alpha_2 = alpha * alpha;
+    let denominator = (n_dot_h * n_dot_h * (alpha_2 - 1.0)) + 1.0;
+
+    alpha_2 / (PI * denominator * denominator)
+}
+
+/// The Smith masking function for the GGX distribution.
+fn smith_g1(n_dot_v: f64, alpha: f64) -> f64 {
+    let alpha_2 = alpha * alpha;
+
+    2.0 * n_dot_v / (n_dot_v + (alpha_2 + ((1.0 - alpha_2) * n_dot_v * n_dot_v)).sqrt())
+}
+
+/// Chooses a microfacet normal with probability proportional to its GGX density times its
+/// cosine with `normal`.
+fn sample_ggx_normal(normal: &Vector, alpha: f64, random: &mut Random) -> Vector {
+    let u = random.next_f64();
+    let cos_theta = ((1.0 - u) / (1.0 + (((alpha * alpha) - 1.0) * u))).sqrt();
+    let sin_theta = (1.0 - (cos_theta * cos_theta)).max(0.0).sqrt();
+    let (sin_phi, cos_phi) = (2.0 * PI * random.next_f64()).sin_cos();
+
+    let (tangent, bitangent) = orthonormal_basis(normal);
+
+    (tangent * (sin_theta * cos_phi))
+        + &(bitangent * (sin_theta * sin_phi))
+        + &(*normal * cos_theta)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::material::microfacet::{Microfacet, ggx_distribution, sample_ggx_normal};
+    use crate::random::Random;
+    use crate::vector::Vector;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+    use std::f64::consts::PI;
+
+    /// Returns a uniformly-distributed direction in the hemisphere around +z.
+    fn uniform_hemisphere(random: &mut Random) -> Vector {
+        let z = random.next_f64();
+        let r = (1.0 - (z * z)).sqrt();
+        let (sin, cos) = (2.0 * PI * random.next_f64()).sin_cos();
+
+        Vector::new(r * cos, r * sin, z)
+    }
+
+    #[test]
+    fn test_distribution_normalized() {
+        // The projected area of all microfacets should equal the macroscopic surface's area
+        let mut random = Random::new(0);
+
+        for alpha in [0.2, 0.5, 1.0] {
+            let mut sum = 0.0;
+
+            for _ in 0..100_000 {
+                let h = uniform_hemisphere(&mut random);
+                sum += ggx_distribution(h.components()[2], alpha) * h.components()[2];
+            }
+
+            assert_float_absolute_eq!(1.0, sum * 2.0 * PI / 100_000.0, 0.05);
+        }
+    }
+
+    #[test]
+    fn test_sample_ggx_normal() {
+        let normal = Vector::new(0.0, 0.0, 1.0);
+        let mut random = Random::new(0);
+
+        for _ in 0..1_000 {
+            let h = sample_ggx_normal(&normal, 0.3, &mut random);
+
+            assert_float_absolute_eq!(1.0, h.magnitude(), 1e-12);
+            assert!(h.dot(&normal) >= 0.0);
+        }
+    }
+
+    #[test]
+    fn test_fresnel() {
+        let glass = Microfacet::new(color::WHITE, 0.0, 0.5, 1.5);
+
+        Color::new(0.04, 0.04, 0.04).assert_approx_eq(&glass.fresnel(1.0));
+        color::WHITE.assert_approx_eq(&glass.fresnel(0.0));
+
+        let gold = Microfacet::new(Color::new(1.0, 0.8, 0.3), 1.0, 0.5, 1.5);
+        Color::new(1.0, 0.8, 0.3).assert_approx_eq(&gold.fresnel(1.0));
+    }
+
+    #[test]
+    fn test_below_surface() {
+        let material = Microfacet::default();
+        let normal = Vector::new(0.0, 0.0, 1.0);
+
+        color::BLACK.assert_approx_eq(&material.evaluate(
+            &normal,
+            &Vector::new(0.0, 0.0, 1.0),
+            &Vector::new(0.0, 0.6, -0.8),
+        ));
+
+        assert_f64_near!(
+            0.0,
+            material.pdf(
+                &normal,
+                &Vector::new(0.0, 0.0, 1.0),
+                &Vector::new(0.0, 0.6, -0.8)
+            )
+        );
+    }
+
+    #[test]
+    fn test_sampling_matches_evaluation() {
+        // Importance-sampled and uniformly-sampled estimates of the directional albedo should
+        // agree, and a white material shouldn't reflect more energy than it receives
+        let normal = Vector::new(0.0, 0.0, 1.0);
+        let eye = Vector::new(0.6, 0.0, 0.8);
+
+        for material in [
+            Microfacet::new(color::WHITE, 0.0, 0.3, 1.5),
+            Microfacet::new(color::WHITE, 1.0, 0.5, 1.5),
+            Microfacet::new(Color::new(0.8, 0.4, 0.2), 0.5, 0.8, 1.5),
+        ] {
+            let mut random = Random::new(0);
+            let (mut sampled, mut uniform) = (color::BLACK, color::BLACK);
+
+            for _ in 0..200_000 {
+                if let Some((_, weight)) = material.sample(&normal, &eye, &mut random) {
+                    sampled = sampled + weight;
+                }
+
+                let light = uniform_hemisphere(&mut random);
+
+                uniform = uniform
+                    + material.evaluate(&normal, &eye, &light) * (light.components()[2] * 2.0 * PI);
+            }
+
+            let (sampled, uniform) = (sampled * (1.0 / 200_000.0), uniform * (1.0 / 200_000.0));
+
+            uniform.assert_approx_eq_epsilon(&sampled, 0.05);
+            assert!(sampled.components().iter().all(|c| *c <= 1.0));
+        }
+    }
+}
@@ -0,0 +1,422 @@
+pub mod microfacet;
+pub mod shading;
+pub mod subsurface;
+
+use crate::color;
+use crate::color::Color;
+use crate::light::Light;
+use crate::material::microfacet::Microfacet;
+use crate::material::shading::{Phong, ShadingModel};
+use crate::material::subsurface::Subsurface;
+use crate::random::Random;
+use crate::sampling::warp::cosine_sample_hemisphere;
+use crate::vector::{Point, Vector};
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct Material {
+    color: Color,
+    ambient: f64,
+    diffuse: f64,
+    specular: f64,
+    shininess: f64,
+    emission: Color,
+    shading: Arc<dyn ShadingModel + Send + Sync>,
+    microfacet: Option<Microfacet>,
+    subsurface: Option<Subsurface>,
+}
+
+impl Material {
+    pub fn new(color: Color, ambient: f64, diffuse: f64, specular: f64, shininess: f64) -> Self {
+        Self {
+            color,
+            ambient,
+            diffuse,
+            specular,
+            shininess,
+            emission: color::BLACK,
+            shading: Arc::new(Phong),
+            microfacet: None,
+            subsurface: None,
+        }
+    }
+
+    /// Creates a physically-based material. Its ambient color is its base color.
+    pub fn physical(microfacet: Microfacet) -> Self {
+        Self {
+            color: *microfacet.base_color(),
+            microfacet: Some(microfacet),
+            ..Self::default()
+        }
+    }
+
+    /// Replaces the Phong shading model used for direct lighting. Physically-based materials
+    /// ignore their shading model.
+    pub fn with_shading<S: ShadingModel + Send + Sync + 'static>(mut self, shading: S) -> Self {
+        self.shading = Arc::new(shading);
+        self
+    }
+
+    /// Makes surfaces with this material glow with the given radiance.
+    pub fn with_emission(mut self, emission: Color) -> Self {
+        self.emission = emission;
+        self
+    }
+
+    /// Makes this material translucent. Light scattered beneath the surface adds to the
+    /// material's diffuse and specular reflection, so translucent materials usually have little
+    /// or no diffuse reflection of their own.
+    pub fn with_subsurface(mut self, subsurface: Subsurface) -> Self {
+        self.subsurface = Some(subsurface);
+        self
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    pub fn ambient(&self) -> f64 {
+        self.ambient
+    }
+
+    pub fn diffuse(&self) -> f64 {
+        self.diffuse
+    }
+
+    pub fn specular(&self) -> f64 {
+        self.specular
+    }
+
+    pub fn shininess(&self) -> f64 {
+        self.shininess
+    }
+
+    pub fn emission(&self) -> &Color {
+        &self.emission
+    }
+
+    pub fn is_emissive(&self) -> bool {
+        self.emission
+            .components()
+            .iter()
+            .any(|component| *component > 0.0)
+    }
+
+    pub fn shading(&self) -> &dyn ShadingModel {
+        self.shading.as_ref()
+    }
+
+    pub fn microfacet(&self) -> Option<&Microfacet> {
+        self.microfacet.as_ref()
+    }
+
+    pub fn subsurface(&self) -> Option<&Subsurface> {
+        self.subsurface.as_ref()
+    }
+
+    /// Computes the color of a point lit by the given light. `visibility` is the fraction of
+    /// the light that reaches the point, and scales diffuse and specular (but not ambient)
+    /// contributions.
+    pub fn lighting(
+        &self,
+        light: &dyn Light,
+        position: &Point,
+        eye: &Vector,
+        normal: &Vector,
+        visibility: f64,
+    ) -> Color {
+        self.ambient_lighting(&light.intensity_at(position))
+            + self.direct_lighting(light, position, eye, normal, visibility)
+    }
+
+    /// Returns the color contributed by ambient light of the given intensity.
+    pub fn ambient_lighting(&self, intensity: &Color) -> Color {
+        self.color * *intensity * self.ambient
+    }
+
+    /// Returns the diffuse and specular contributions of a single light, scaled by the
+    /// fraction of the light that reaches the point. Physically-based materials are scaled by π
+    /// so that their diffuse lobes are as bright as a Phong material's diffuse reflection.
+    pub fn direct_lighting(
+        &self,
+        light: &dyn Light,
+        position: &Point,
+        eye: &Vector,
+        normal: &Vector,
+        visibility: f64,
+    ) -> Color {
+        self.reflected(
+            &light.direction_from(position),
+            &light.intensity_at(position),
+            eye,
+            normal,
+        ) * visibility
+    }
+
+    /// Returns the diffuse and specular light reflected toward `eye` from unshadowed light of
+    /// the given intensity arriving from `light_vector`.
+    pub fn reflected(
+        &self,
+        light_vector: &Vector,
+        intensity: &Color,
+        eye: &Vector,
+        normal: &Vector,
+    ) -> Color {
+        if let Some(microfacet) = &self.microfacet {
+            let cos = light_vector.dot(normal).max(0.0);
+
+            return microfacet.evaluate(normal, eye, light_vector) * *intensity * (cos * PI);
+        }
+
+        self.shading
+            .shade(self, light_vector, eye, normal, intensity)
+    }
+
+    /// Returns the value of this material's BRDF for light arriving from `light` and leaving
+    /// toward `eye`. Phong materials are treated as Lambertian reflectors with albedo
+    /// `color × diffuse`.
+    pub fn brdf(&self, normal: &Vector, eye: &Vector, light: &Vector) -> Color {
+        match &self.microfacet {
+            Some(microfacet) => microfacet.evaluate(normal, eye, light),
+            None if normal.dot(light) > 0.0 => self.color * (self.diffuse / PI),
+            None => color::BLACK,
+        }
+    }
+
+    /// Chooses a direction from which to gather light toward `eye` and returns it along with
+    /// the BRDF times the cosine term divided by the sample's probability density, or `None` if
+    /// the path should end.
+    pub fn sample_brdf(
+        &self,
+        normal: &Vector,
+        eye: &Vector,
+        random: &mut Random,
+    ) -> Option<(Vector, Color)> {
+        match &self.microfacet {
+            Some(microfacet) => microfacet.sample(normal, eye, random),
+            // Cosine-weighted sampling cancels the cosine and 1/π terms of the Lambertian BRDF,
+            // leaving just the albedo
+            None => Some((
+                cosine_sample_hemisphere(normal, random),
+                self.color * self.diffuse,
+            )),
+        }
+    }
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material {
+            color: color::WHITE,
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.9,
+            shininess: 200.0,
+            emission: color::BLACK,
+            shading: Arc::new(Phong),
+            microfacet: None,
+            subsurface: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color::Color;
+    use crate::light::area::AreaLight;
+    use crate::light::directional::DirectionalLight;
+    use crate::light::point::PointLight;
+    use crate::light::spot::SpotLight;
+    use crate::material::Material;
+    use crate::material::microfacet::Microfacet;
+    use crate::vector::{Point, Vector};
+    use crate::{color, vector};
+
+    #[test]
+    fn test_lighting_light_behind_viewer() {
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), color::WHITE);
+
+        Color::new(1.9, 1.9, 1.9).assert_approx_eq(&Material::default().lighting(
+            &light,
+            &vector::ORIGIN,
+            &eye,
+            &normal,
+            1.0,
+        ));
+    }
+
+    #[test]
+    fn test_lighting_eye_off_normal() {
+        let sqrt_2_2 = 2.0f64.sqrt() / 2.0;
+
+        let eye = Vector::new(0.0, sqrt_2_2, -sqrt_2_2);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), color::WHITE);
+
+        Color::new(1.0, 1.0, 1.0).assert_approx_eq(&Material::default().lighting(
+            &light,
+            &vector::ORIGIN,
+            &eye,
+            &normal,
+            1.0,
+        ));
+    }
+
+    #[test]
+    fn test_lighting_light_off_normal() {
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 10.0, -10.0), color::WHITE);
+
+        Color::new(0.7364, 0.7364, 0.7364).assert_approx_eq_epsilon(
+            &Material::default().lighting(&light, &vector::ORIGIN, &eye, &normal, 1.0),
+            1e-4,
+        );
+    }
+
+    #[test]
+    fn test_lighting_eye_and_light_off_normal() {
+        let sqrt_2_2 = 2.0f64.sqrt() / 2.0;
+
+        let eye = Vector::new(0.0, -sqrt_2_2, -sqrt_2_2);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 10.0, -10.0), color::WHITE);
+
+        Color::new(1.6364, 1.6364, 1.6364).assert_approx_eq_epsilon(
+            &Material::default().lighting(&light, &vector::ORIGIN, &eye, &normal, 1.0),
+            1e-4,
+        );
+    }
+
+    #[test]
+    fn test_lighting_light_behind_surface() {
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, 10.0), color::WHITE);
+
+        Color::new(0.1, 0.1, 0.1).assert_approx_eq(&Material::default().lighting(
+            &light,
+            &vector::ORIGIN,
+            &eye,
+            &normal,
+            1.0,
+        ));
+    }
+
+    #[test]
+    fn test_lighting_partially_visible() {
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), color::WHITE);
+
+        // Shadowed points receive only ambient light
+        Color::new(0.1, 0.1, 0.1).assert_approx_eq(&Material::default().lighting(
+            &light,
+            &vector::ORIGIN,
+            &eye,
+            &normal,
+            0.0,
+        ));
+
+        Color::new(1.0, 1.0, 1.0).assert_approx_eq(&Material::default().lighting(
+            &light,
+            &vector::ORIGIN,
+            &eye,
+            &normal,
+            0.5,
+        ));
+    }
+
+    #[test]
+    fn test_area_lighting() {
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+
+        let light = AreaLight::rectangle(
+            Point::new(-1.0, -1.0, -10.0),
+            Vector::new(2.0, 0.0, 0.0),
+            Vector::new(0.0, 2.0, 0.0),
+            color::WHITE,
+            4,
+        );
+
+        Color::new(1.9, 1.9, 1.9).assert_approx_eq(&Material::default().lighting(
+            &light,
+            &vector::ORIGIN,
+            &eye,
+            &normal,
+            1.0,
+        ));
+    }
+
+    #[test]
+    fn test_lighting_directional_and_spot() {
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+
+        let directional = DirectionalLight::new(Vector::new(0.0, 0.0, 1.0), color::WHITE);
+
+        Color::new(1.9, 1.9, 1.9).assert_approx_eq(&Material::default().lighting(
+            &directional,
+            &vector::ORIGIN,
+            &eye,
+            &normal,
+            1.0,
+        ));
+
+        // A spot light pointing away from the surface contributes nothing
+        let spot = SpotLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            Vector::new(0.0, 0.0, -1.0),
+            0.1,
+            0.2,
+            color::WHITE,
+        );
+
+        color::BLACK.assert_approx_eq(&Material::default().lighting(
+            &spot,
+            &vector::ORIGIN,
+            &eye,
+            &normal,
+            1.0,
+        ));
+    }
+
+    #[test]
+    fn test_physical_lighting() {
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 10.0, -10.0), color::WHITE);
+        let light_vector = Vector::new(0.0, 1.0, -1.0).normalize();
+
+        let microfacet = Microfacet::new(Color::new(0.8, 0.2, 0.2), 0.0, 0.4, 1.5);
+        let material = Material::physical(microfacet);
+
+        let expected = (microfacet.evaluate(&normal, &eye, &light_vector)
+            * (light_vector.dot(&normal) * std::f64::consts::PI))
+            + (Color::new(0.8, 0.2, 0.2) * 0.1);
+
+        expected.assert_approx_eq(&material.lighting(&light, &vector::ORIGIN, &eye, &normal, 1.0));
+        microfacet
+            .evaluate(&normal, &eye, &light_vector)
+            .assert_approx_eq(&material.brdf(&normal, &eye, &light_vector));
+
+        // Phong materials act as Lambertian reflectors for stochastic integrators
+        (color::WHITE * (0.9 / std::f64::consts::PI)).assert_approx_eq(&Material::default().brdf(
+            &normal,
+            &eye,
+            &light_vector,
+        ));
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        // Materials can be shared between rendering threads
+        assert_send_sync::<Material>();
+    }
+}
@@ -0,0 +1,388 @@
+use crate::color;
+use crate::color::Color;
+use crate::material::Material;
+use crate::vector::Vector;
+
+/// Computes the light reflected toward the eye from a single light, using a material's color,
+/// diffuse, specular and shininess parameters.
+pub trait ShadingModel {
+    /// Returns the diffuse and specular light reflected toward `eye` from a light of the given
+    /// `intensity` in the direction `light_vector`. All vectors are unit vectors pointing away
+    /// from the surface.
+    fn shade(
+        &self,
+        material: &Material,
+        light_vector: &Vector,
+        eye: &Vector,
+        normal: &Vector,
+        intensity: &Color,
+    ) -> Color;
+}
+
+/// The classic Phong model, with specular highlights based on the angle between the eye and the
+/// light's mirror reflection.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Phong;
+
+impl ShadingModel for Phong {
+    fn shade(
+        &self,
+        material: &Material,
+        light_vector: &Vector,
+        eye: &Vector,
+        normal: &Vector,
+        intensity: &Color,
+    ) -> Color {
+        let light_dot_normal = light_vector.dot(normal);
+
+        // A negative light • normal indicates that the cosine of the angle between the vectors is
+        // negative, and the light is behind the surface
+        if light_dot_normal < 0.0 {
+            return color::BLACK;
+        }
+
+        let diffuse = *material.color() * *intensity * material.diffuse() * light_dot_normal;
+
+        let reflect = (-*light_vector).reflect(normal);
+        let reflect_dot_eye = reflect.dot(eye);
+
+        // A negative reflect • eye indicates that the reflection vector points away from the
+        // eye vector
+        let specular = if reflect_dot_eye < 0.0 {
+            color::BLACK
+        } else {
+            *intensity * material.specular() * reflect_dot_eye.powf(material.shininess())
+        };
+
+        diffuse + specular
+    }
+}
+
+/// The Blinn-Phong model, with specular highlights based on the angle between the normal and the
+/// vector halfway between the eye and the light. Highlights are broader than Phong's for the
+/// same shininess.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BlinnPhong;
+
+impl ShadingModel for BlinnPhong {
+    fn shade(
+        &self,
+        material: &Material,
+        light_vector: &Vector,
+        eye: &Vector,
+        normal: &Vector,
+        intensity: &Color,
+    ) -> Color {
+        let light_dot_normal = light_vector.dot(normal);
+
+        if light_dot_normal < 0.0 {
+            return color::BLACK;
+        }
+
+        let diffuse = *material.color() * *intensity * material.diffuse() * light_dot_normal;
+
+        let half = (*light_vector + eye).normalize();
+        let half_dot_normal = half.dot(normal);
+
+        let specular = if half_dot_normal < 0.0 {
+            color::BLACK
+        } else {
+            *intensity * material.specular() * half_dot_normal.powf(material.shininess())
+        };
+
+        diffuse + specular
+    }
+}
+
+/// A perfectly matte surface that scatters light equally in all directions, with no specular
+/// highlights.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Lambert;
+
+impl ShadingModel for Lambert {
+    fn shade(
+        &self,
+        material: &Material,
+        light_vector: &Vector,
+        _eye: &Vector,
+        normal: &Vector,
+        intensity: &Color,
+    ) -> Color {
+        let light_dot_normal = light_vector.dot(normal);
+
+        if light_dot_normal < 0.0 {
+            return color::BLACK;
+        }
+
+        *material.color() * *intensity * material.diffuse() * light_dot_normal
+    }
+}
+
+/// The Oren-Nayar model of rough, matte surfaces (like clay or the moon), which appear flatter
+/// and brighter toward the viewer than Lambertian ones. Roughness is the standard deviation, in
+/// radians, of the angles of the surface's microscopic facets; a roughness of zero is Lambertian.
+/// There are no specular highlights.
+#[derive(Copy, Clone, Debug)]
+pub struct OrenNayar {
+    roughness: f64,
+    a: f64,
+    b: f64,
+}
+
+impl OrenNayar {
+    pub fn new(roughness: f64) -> Self {
+        let sigma_2 = roughness * roughness;
+
+        Self {
+            roughness,
+            a: 1.0 - (0.5 * sigma_2 / (sigma_2 + 0.33)),
+            b: 0.45 * sigma_2 / (sigma_2 + 0.09),
+        }
+    }
+
+    pub fn roughness(&self) -> f64 {
+        self.roughness
+    }
+}
+
+impl ShadingModel for OrenNayar {
+    fn shade(
+        &self,
+        material: &Material,
+        light_vector: &Vector,
+        eye: &Vector,
+        normal: &Vector,
+        intensity: &Color,
+    ) -> Color {
+        let cos_light = light_vector.dot(normal);
+
+        if cos_light < 0.0 {
+            return color::BLACK;
+        }
+
+        let cos_eye = eye.dot(normal).clamp(-1.0, 1.0);
+        let (theta_light, theta_eye) = (cos_light.min(1.0).acos(), cos_eye.acos());
+
+        // The cosine of the difference in azimuth between the light and the eye, found by
+        // projecting both onto the surface's tangent plane
+        let light_tangent = *light_vector - &(*normal * cos_light);
+        let eye_tangent = *eye - &(*normal * cos_eye);
+        let tangent_lengths = light_tangent.magnitude() * eye_tangent.magnitude();
+
+        let cos_azimuth = if tangent_lengths > f64::EPSILON {
+            light_tangent.dot(&eye_tangent) / tangent_lengths
+        } else {
+            0.0
+        };
+
+        let alpha = theta_light.max(theta_eye);
+        let beta = theta_light.min(theta_eye);
+
+        let factor = self.a + (self.b * cos_azimuth.max(0.0) * alpha.sin() * beta.tan());
+
+        *material.color() * *intensity * (material.diffuse() * cos_light * factor)
+    }
+}
+
+impl Default for OrenNayar {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color::Color;
+    use crate::light::point::PointLight;
+    use crate::material::Material;
+    use crate::material::shading::{BlinnPhong, Lambert, OrenNayar, ShadingModel};
+    use crate::vector;
+    use crate::vector::{Point, Vector};
+
+    /// Lights the origin, whose normal points toward -z, as in the `test_lighting_*` cases for
+    /// the default Phong model.
+    fn lighting<S: ShadingModel + Send + Sync + 'static>(
+        model: S,
+        eye: Vector,
+        light: Point,
+    ) -> Color {
+        Material::default().with_shading(model).lighting(
+            &PointLight::new(light, crate::color::WHITE),
+            &vector::ORIGIN,
+            &eye,
+            &Vector::new(0.0, 0.0, -1.0),
+            1.0,
+        )
+    }
+
+    fn gray(value: f64) -> Color {
+        Color::new(value, value, value)
+    }
+
+    #[test]
+    fn test_blinn_phong() {
+        let sqrt_2_2 = 2.0f64.sqrt() / 2.0;
+
+        // Light behind the viewer
+        gray(1.9).assert_approx_eq_epsilon(
+            &lighting(
+                BlinnPhong,
+                Vector::new(0.0, 0.0, -1.0),
+                Point::new(0.0, 0.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        // Eye off the normal
+        gray(1.0).assert_approx_eq_epsilon(
+            &lighting(
+                BlinnPhong,
+                Vector::new(0.0, sqrt_2_2, -sqrt_2_2),
+                Point::new(0.0, 0.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        // Light off the normal
+        gray(0.7364).assert_approx_eq_epsilon(
+            &lighting(
+                BlinnPhong,
+                Vector::new(0.0, 0.0, -1.0),
+                Point::new(0.0, 10.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        // Eye and light off the normal; the half vector lines up with the normal
+        gray(1.6364).assert_approx_eq_epsilon(
+            &lighting(
+                BlinnPhong,
+                Vector::new(0.0, -sqrt_2_2, -sqrt_2_2),
+                Point::new(0.0, 10.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        // Light behind the surface
+        gray(0.1).assert_approx_eq(&lighting(
+            BlinnPhong,
+            Vector::new(0.0, 0.0, -1.0),
+            Point::new(0.0, 0.0, 10.0),
+        ));
+    }
+
+    #[test]
+    fn test_lambert() {
+        let sqrt_2_2 = 2.0f64.sqrt() / 2.0;
+
+        gray(1.0).assert_approx_eq(&lighting(
+            Lambert,
+            Vector::new(0.0, 0.0, -1.0),
+            Point::new(0.0, 0.0, -10.0),
+        ));
+
+        gray(1.0).assert_approx_eq(&lighting(
+            Lambert,
+            Vector::new(0.0, sqrt_2_2, -sqrt_2_2),
+            Point::new(0.0, 0.0, -10.0),
+        ));
+
+        gray(0.7364).assert_approx_eq_epsilon(
+            &lighting(
+                Lambert,
+                Vector::new(0.0, 0.0, -1.0),
+                Point::new(0.0, 10.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        gray(0.7364).assert_approx_eq_epsilon(
+            &lighting(
+                Lambert,
+                Vector::new(0.0, -sqrt_2_2, -sqrt_2_2),
+                Point::new(0.0, 10.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        gray(0.1).assert_approx_eq(&lighting(
+            Lambert,
+            Vector::new(0.0, 0.0, -1.0),
+            Point::new(0.0, 0.0, 10.0),
+        ));
+    }
+
+    #[test]
+    fn test_oren_nayar() {
+        let sqrt_2_2 = 2.0f64.sqrt() / 2.0;
+        let model = OrenNayar::new(0.5);
+
+        gray(0.8060).assert_approx_eq_epsilon(
+            &lighting(
+                model,
+                Vector::new(0.0, 0.0, -1.0),
+                Point::new(0.0, 0.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        gray(0.8060).assert_approx_eq_epsilon(
+            &lighting(
+                model,
+                Vector::new(0.0, sqrt_2_2, -sqrt_2_2),
+                Point::new(0.0, 0.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        gray(0.5992).assert_approx_eq_epsilon(
+            &lighting(
+                model,
+                Vector::new(0.0, 0.0, -1.0),
+                Point::new(0.0, 10.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        // The eye and light are on opposite sides of the normal, so there's no retroreflection
+        gray(0.5992).assert_approx_eq_epsilon(
+            &lighting(
+                model,
+                Vector::new(0.0, -sqrt_2_2, -sqrt_2_2),
+                Point::new(0.0, 10.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        // Looking back along the light, rough surfaces appear brighter than Lambertian ones
+        gray(0.7481).assert_approx_eq_epsilon(
+            &lighting(
+                model,
+                Vector::new(0.0, sqrt_2_2, -sqrt_2_2),
+                Point::new(0.0, 10.0, -10.0),
+            ),
+            1e-4,
+        );
+
+        gray(0.1).assert_approx_eq(&lighting(
+            model,
+            Vector::new(0.0, 0.0, -1.0),
+            Point::new(0.0, 0.0, 10.0),
+        ));
+
+        // Without roughness, Oren-Nayar is Lambertian
+        lighting(
+            Lambert,
+            Vector::new(0.0, sqrt_2_2, -sqrt_2_2),
+            Point::new(0.0, 10.0, -10.0),
+        )
+        .assert_approx_eq_epsilon(
+            &lighting(
+                OrenNayar::new(0.0),
+                Vector::new(0.0, sqrt_2_2, -sqrt_2_2),
+                Point::new(0.0, 10.0, -10.0),
+            ),
+            1e-9,
+        );
+    }
+}
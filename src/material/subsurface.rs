@@ -0,0 +1,238 @@
+use crate::color::Color;
+use crate::random::Random;
+use crate::ray::Ray;
+use crate::sampling::warp::{cosine_sample_hemisphere, uniform_sample_sphere};
+use crate::shape::Shape;
+use crate::vector::{Point, Vector};
+
+/// The most times light may scatter inside a shape. Walks still inside after this many bounces
+/// are cut off and treated as absorbed, which darkens materials whose mean free path is tiny
+/// compared to the shape (such materials look much the same rendered as opaque).
+const MAX_BOUNCES: usize = 1024;
+
+/// How many times light scatters before walks that carry little light start to be ended at
+/// random (Russian roulette).
+const ROULETTE_BOUNCES: usize = 8;
+
+/// How far inside a shape's surface random walks start, so that they don't immediately leave
+/// through the surface they entered.
+const WALK_EPSILON: f64 = 1e-6;
+
+/// Light scattering beneath the surface of a translucent material like skin, wax or marble,
+/// simulated by random walks through the interior of a shape. Each color channel has its own
+/// mean free path (the average distance light travels between scattering events) and its own
+/// single-scattering albedo (the fraction of light that survives each event).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Subsurface {
+    albedo: Color,
+    mean_free_path: Color,
+}
+
+impl Subsurface {
+    pub fn new(albedo: Color, mean_free_path: Color) -> Self {
+        Self {
+            albedo,
+            mean_free_path,
+        }
+    }
+
+    pub fn albedo(&self) -> &Color {
+        &self.albedo
+    }
+
+    pub fn mean_free_path(&self) -> &Color {
+        &self.mean_free_path
+    }
+
+    /// Follows light that enters `shape` at `position`, where the surface has the outward normal
+    /// `normal`, until it leaves the shape as it is at the given time. Returns the point at which
+    /// it leaves, the outward normal there, and the fraction of the light in each color channel
+    /// that makes it out, or `None` if it is absorbed or is still inside after `MAX_BOUNCES`
+    /// bounces.
+    pub fn walk(
+        &self,
+        shape: &dyn Shape,
+        position: &Point,
+        normal: &Vector,
+        time: f64,
+        random: &mut Random,
+    ) -> Option<(Point, Vector, Color)> {
+        let mean_free_path = self.mean_free_path.components();
+        let extinction = [0, 1, 2].map(|c| 1.0 / mean_free_path[c].max(f64::EPSILON));
+        let albedo = self.albedo.components();
+
+        let mut point = *position - &(*normal * WALK_EPSILON);
+        let mut direction = cosine_sample_hemisphere(&-*normal, random);
+        let mut throughput = [1.0; 3];
+
+        for bounce in 0..MAX_BOUNCES {
+            // Choose the distance to the next scattering event with one channel's extinction,
+            // weighting each channel by its own probability over the average of all channels'
+            // (the balance heuristic)
+            let channel = ((random.next_f64() * 3.0) as usize).min(2);
+            let distance = -(1.0 - random.next_f64()).ln() / extinction[channel];
+
+            let ray = Ray::new(point, direction).with_time(time);
+            let boundary = shape
+                .intersect(&ray)
+                .iter()
+                .map(|intersection| intersection.distance())
+                .filter(|distance| *distance > WALK_EPSILON)
+                .fold(f64::INFINITY, f64::min);
+
+            if boundary <= distance {
+                // The probability of getting this far without scattering is the transmittance
+                let transmittance = extinction.map(|extinction| (-extinction * boundary).exp());
+                let mean = transmittance.iter().sum::<f64>() / 3.0;
+
+                for c in 0..3 {
+                    throughput[c] *= transmittance[c] / mean;
+                }
+
+                let exit = ray.position(boundary);
+
+                return Some((
+                    exit,
+                    shape.normal_at(&exit, time),
+                    Color::new(throughput[0], throughput[1], throughput[2]),
+                ));
+            }
+
+            let transmittance = extinction.map(|extinction| (-extinction * distance).exp());
+            let mean = (0..3)
+                .map(|c| extinction[c] * transmittance[c])
+                .sum::<f64>()
+                / 3.0;
+
+            for c in 0..3 {
+                throughput[c] *= albedo[c] * extinction[c] * transmittance[c] / mean;
+            }
+
+            // End walks in proportion to how little light they carry, boosting the survivors to
+            // compensate
+            if bounce >= ROULETTE_BOUNCES {
+                let continue_probability = throughput.iter().copied().fold(0.0, f64::max).min(1.0);
+
+                if random.next_f64() >= continue_probability {
+                    return None;
+                }
+
+                throughput = throughput.map(|component| component / continue_probability);
+            } else if throughput.iter().all(|component| *component <= 0.0) {
+                return None;
+            }
+
+            point = ray.position(distance);
+            direction = uniform_sample_sphere(random);
+        }
+
+        None
+    }
+}
+
+impl Default for Subsurface {
+    /// Creates a marble-like material that scatters blue light slightly farther than red.
+    fn default() -> Self {
+        Self::new(Color::new(0.99, 0.98, 0.97), Color::new(0.2, 0.25, 0.3))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::material::Material;
+    use crate::material::subsurface::Subsurface;
+    use crate::random::Random;
+    use crate::shape::Shape;
+    use crate::shape::sphere::Sphere;
+    use crate::vector::{Point, Vector};
+    use assert_float_eq::assert_float_absolute_eq;
+
+    #[test]
+    fn test_walk_lossless() {
+        let sphere = Sphere::with_transformations(&[], Material::default());
+        let subsurface = Subsurface::new(color::WHITE, Color::new(0.25, 0.25, 0.25));
+        let position = Point::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let mut random = Random::new(0);
+
+        // Without absorption, all light eventually leaves the sphere through its surface
+        for _ in 0..100 {
+            let (exit, exit_normal, weight) = subsurface
+                .walk(&sphere, &position, &normal, 0.0, &mut random)
+                .unwrap();
+
+            assert_float_absolute_eq!(1.0, (exit - &Point::new(0.0, 0.0, 0.0)).magnitude(), 1e-6);
+            exit_normal.assert_approx_eq_epsilon(&sphere.normal_at(&exit, 0.0), 1e-12);
+            color::WHITE.assert_approx_eq_epsilon(&weight, 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_walk_mean_free_path() {
+        let sphere = Sphere::with_transformations(&[], Material::default());
+        let position = Point::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let mut random = Random::new(0);
+
+        // Light wanders farther from where it entered in materials with longer mean free paths
+        let mut mean_spread = |mean_free_path: f64| {
+            let subsurface = Subsurface::new(
+                color::WHITE,
+                Color::new(mean_free_path, mean_free_path, mean_free_path),
+            );
+
+            let spreads: Vec<f64> = (0..1_000)
+                .filter_map(|_| subsurface.walk(&sphere, &position, &normal, 0.0, &mut random))
+                .map(|(exit, _, _)| (exit - &position).magnitude())
+                .collect();
+
+            spreads.iter().sum::<f64>() / spreads.len() as f64
+        };
+
+        assert!(mean_spread(0.02) < mean_spread(0.2));
+
+        // Absorbing materials lose light
+        let subsurface = Subsurface::new(Color::new(0.9, 0.9, 0.9), Color::new(0.01, 0.01, 0.1));
+        let mut sum = color::BLACK;
+
+        for _ in 0..1_000 {
+            if let Some((_, _, weight)) =
+                subsurface.walk(&sphere, &position, &normal, 0.0, &mut random)
+            {
+                sum = sum + weight;
+            }
+        }
+
+        let mean = sum * (1.0 / 1_000.0);
+
+        assert!(mean.components().iter().all(|component| *component < 1.0));
+    }
+
+    #[test]
+    fn test_walk_russian_roulette() {
+        let sphere = Sphere::with_transformations(&[], Material::default());
+        let position = Point::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let mut random = Random::new(0);
+
+        // Walks whose red channel is lossless are never ended early, so their green channel is an
+        // estimate without Russian roulette to compare against
+        let mut mean_green = |albedo: Color| {
+            let subsurface = Subsurface::new(albedo, Color::new(0.05, 0.05, 0.05));
+
+            (0..4_000)
+                .filter_map(|_| subsurface.walk(&sphere, &position, &normal, 0.0, &mut random))
+                .map(|(_, _, weight)| weight.components()[1])
+                .sum::<f64>()
+                / 4_000.0
+        };
+
+        assert_float_absolute_eq!(
+            mean_green(Color::new(1.0, 0.95, 0.95)),
+            mean_green(Color::new(0.95, 0.95, 0.95)),
+            0.02
+        );
+    }
+}
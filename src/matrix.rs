@@ -1,3 +1,5 @@
+use crate::decomposition::{Decomposition, DecompositionError};
+use crate::simd;
 use crate::vector::{Point, Vector};
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::{Index, Mul};
@@ -5,6 +7,7 @@ use std::ops::{Index, Mul};
 #[cfg(test)]
 use assert_float_eq::assert_float_absolute_eq;
 
+#[derive(Clone)]
 pub struct Matrix<const N: usize> {
     elements: [[f64; N]; N],
 }
@@ -47,6 +50,11 @@ impl<const N: usize> Matrix<N> {
 }
 
 impl Matrix<4> {
+    /// Splits this affine transformation matrix into a scale, shear, rotation and translation.
+    pub fn decompose(&self) -> Result<Decomposition, DecompositionError> {
+        Decomposition::try_from(self)
+    }
+
     pub fn inverse(&self) -> Result<Matrix<4>, SingularMatrix> {
         let determinant = self.determinant();
 
@@ -75,7 +83,11 @@ impl Matrix<4> {
     fn cofactor(&self, row: usize, col: usize) -> f64 {
         let minor = self.submatrix(row, col).determinant();
 
-        if (row + col) % 2 == 0 { minor } else { -minor }
+        if (row + col).is_multiple_of(2) {
+            minor
+        } else {
+            -minor
+        }
     }
 
     fn submatrix(&self, removed_row: usize, removed_col: usize) -> Matrix<3> {
@@ -137,7 +149,11 @@ impl Matrix<3> {
         let minor = (self.elements[rows[0]][cols[0]] * self.elements[rows[1]][cols[1]])
             - (self.elements[rows[0]][cols[1]] * self.elements[rows[1]][cols[0]]);
 
-        if (row + col) % 2 == 0 { minor } else { -minor }
+        if (row + col).is_multiple_of(2) {
+            minor
+        } else {
+            -minor
+        }
     }
 
     fn remaining_indices(n: usize) -> [usize; 2] {
@@ -171,6 +187,18 @@ impl<const N: usize> Mul<&Matrix<N>> for &Matrix<N> {
 
     fn mul(self, rhs: &Matrix<N>) -> Self::Output {
         let mut product = Matrix::new([[0.0; N]; N]);
+
+        // Transformation matrices are all 4×4, and have their own (vectorized) product
+        if N == 4 {
+            let elements = simd::mul_matrix(as_4x4(&self.elements), as_4x4(&rhs.elements));
+            product
+                .elements
+                .as_flattened_mut()
+                .copy_from_slice(elements.as_flattened());
+
+            return product;
+        }
+
         let rhs = rhs.transpose();
 
         for m in 0..N {
@@ -187,6 +215,16 @@ impl<const N: usize> Mul<&Matrix<N>> for &Matrix<N> {
     }
 }
 
+/// Views the elements of a matrix known to be 4×4 as such. Panics for matrices of any other size.
+fn as_4x4<const N: usize>(elements: &[[f64; N]; N]) -> &[[f64; 4]; 4] {
+    elements
+        .as_flattened()
+        .as_chunks::<4>()
+        .0
+        .try_into()
+        .unwrap()
+}
+
 impl<const N: usize> Mul<[f64; N]> for &Matrix<N> {
     type Output = [f64; N];
 
@@ -216,7 +254,7 @@ impl Mul<&Point> for &Matrix<4> {
     type Output = Point;
 
     fn mul(self, rhs: &Point) -> Self::Output {
-        Point::from(self * rhs.components())
+        Point::from(simd::mul_matrix_vector(&self.elements, &rhs.components()))
     }
 }
 
@@ -224,7 +262,7 @@ impl Mul<&Vector> for &Matrix<4> {
     type Output = Vector;
 
     fn mul(self, rhs: &Vector) -> Self::Output {
-        Vector::from(self * rhs.components())
+        Vector::from(simd::mul_matrix_vector(&self.elements, &rhs.components()))
     }
 }
 
@@ -0,0 +1,51 @@
+use crate::projection::Projection;
+use crate::vector::{ORIGIN, Point, Vector};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// A 360° panoramic projection that maps longitude across the canvas and latitude down it, as
+/// used for VR panoramas and environment maps. The center of the canvas looks toward -z, and
+/// canvases should be twice as wide as they are tall to keep the image undistorted.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Equirectangular;
+
+impl Projection for Equirectangular {
+    fn ray(&self, u: f64, v: f64, _aspect: f64) -> Option<(Point, Vector)> {
+        let longitude = ((2.0 * u) - 1.0) * PI;
+        let latitude = (1.0 - (2.0 * v)) * FRAC_PI_2;
+
+        let (sin_longitude, cos_longitude) = longitude.sin_cos();
+        let (sin_latitude, cos_latitude) = latitude.sin_cos();
+
+        Some((
+            ORIGIN,
+            Vector::new(
+                cos_latitude * sin_longitude,
+                sin_latitude,
+                -cos_latitude * cos_longitude,
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::projection::Projection;
+    use crate::projection::equirectangular::Equirectangular;
+    use crate::vector::Vector;
+
+    #[test]
+    fn test_ray() {
+        for (u, v, expected) in [
+            (0.5, 0.5, Vector::new(0.0, 0.0, -1.0)),
+            (0.75, 0.5, Vector::new(1.0, 0.0, 0.0)),
+            (0.25, 0.5, Vector::new(-1.0, 0.0, 0.0)),
+            (0.0, 0.5, Vector::new(0.0, 0.0, 1.0)),
+            (0.5, 0.0, Vector::new(0.0, 1.0, 0.0)),
+            (0.5, 1.0, Vector::new(0.0, -1.0, 0.0)),
+        ] {
+            let (_, direction) = Equirectangular.ray(u, v, 2.0).unwrap();
+
+            expected.assert_approx_eq_epsilon(&direction, 1e-12);
+        }
+    }
+}
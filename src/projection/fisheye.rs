@@ -0,0 +1,71 @@
+use crate::projection::{Projection, screen_coordinates};
+use crate::vector::{ORIGIN, Point, Vector};
+
+/// An equidistant fisheye projection, in which the distance of a point from the center of the
+/// image is proportional to its angle from the view direction. The field of view, in radians,
+/// may exceed π and covers a circle inscribed in the canvas's longer side; points outside the
+/// circle aren't covered.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fisheye {
+    field_of_view: f64,
+}
+
+impl Fisheye {
+    pub fn new(field_of_view: f64) -> Self {
+        Self { field_of_view }
+    }
+
+    pub fn field_of_view(&self) -> f64 {
+        self.field_of_view
+    }
+}
+
+impl Projection for Fisheye {
+    fn ray(&self, u: f64, v: f64, aspect: f64) -> Option<(Point, Vector)> {
+        let (x, y) = screen_coordinates(u, v, aspect);
+        let radius = x.hypot(y);
+
+        if radius > 1.0 {
+            return None;
+        }
+
+        let (sin_theta, cos_theta) = (radius * self.field_of_view / 2.0).sin_cos();
+        let (sin_phi, cos_phi) = y.atan2(x).sin_cos();
+
+        Some((
+            ORIGIN,
+            Vector::new(sin_theta * cos_phi, sin_theta * sin_phi, -cos_theta),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::projection::Projection;
+    use crate::projection::fisheye::Fisheye;
+    use crate::vector::Vector;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_ray() {
+        let fisheye = Fisheye::new(PI);
+
+        let (_, direction) = fisheye.ray(0.5, 0.5, 1.0).unwrap();
+        Vector::new(0.0, 0.0, -1.0).assert_approx_eq(&direction);
+
+        // The edge of the image circle looks 90° to the side, and the corners are outside it
+        let (_, direction) = fisheye.ray(1.0, 0.5, 1.0).unwrap();
+        Vector::new(1.0, 0.0, 0.0).assert_approx_eq_epsilon(&direction, 1e-12);
+
+        let (_, direction) = fisheye.ray(0.5, 0.0, 1.0).unwrap();
+        Vector::new(0.0, 1.0, 0.0).assert_approx_eq_epsilon(&direction, 1e-12);
+
+        assert!(fisheye.ray(0.0, 0.0, 1.0).is_none());
+
+        // Angles grow linearly with distance from the center
+        let (_, direction) = fisheye.ray(0.75, 0.5, 1.0).unwrap();
+        Vector::new(1.0, 0.0, -1.0)
+            .normalize()
+            .assert_approx_eq_epsilon(&direction, 1e-12);
+    }
+}
@@ -0,0 +1,52 @@
+pub mod equirectangular;
+pub mod fisheye;
+pub mod orthographic;
+pub mod perspective;
+
+use crate::vector::{Point, Vector};
+
+/// Maps points on a canvas to the rays that a camera casts through them. Rays are expressed in
+/// the camera's own space, in which the camera sits at the origin looking toward -z with +y up.
+pub trait Projection {
+    /// Returns the origin and direction of the ray through the point (`u`, `v`) on a canvas
+    /// whose width is `aspect` times its height, where (0, 0) is the canvas's top left corner
+    /// and (1, 1) its bottom right, or `None` if the projection doesn't cover that point.
+    fn ray(&self, u: f64, v: f64, aspect: f64) -> Option<(Point, Vector)>;
+}
+
+/// Converts canvas coordinates (see `Projection::ray`) to coordinates centered on the canvas
+/// that run from -1 to 1 along its longer side, with y increasing upward.
+pub fn screen_coordinates(u: f64, v: f64, aspect: f64) -> (f64, f64) {
+    let (x, y) = ((2.0 * u) - 1.0, 1.0 - (2.0 * v));
+
+    if aspect >= 1.0 {
+        (x, y / aspect)
+    } else {
+        (x * aspect, y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::projection::screen_coordinates;
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_screen_coordinates() {
+        let (x, y) = screen_coordinates(0.5, 0.5, 2.0);
+
+        assert_f64_near!(0.0, x);
+        assert_f64_near!(0.0, y);
+
+        // The longer side spans [-1, 1]
+        let (x, y) = screen_coordinates(0.0, 0.0, 2.0);
+
+        assert_f64_near!(-1.0, x);
+        assert_f64_near!(0.5, y);
+
+        let (x, y) = screen_coordinates(1.0, 1.0, 0.5);
+
+        assert_f64_near!(0.5, x);
+        assert_f64_near!(-1.0, y);
+    }
+}
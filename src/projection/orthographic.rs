@@ -0,0 +1,55 @@
+use crate::projection::{Projection, screen_coordinates};
+use crate::vector::{Point, Vector};
+
+/// A parallel projection, in which all rays travel in the same direction and objects keep their
+/// size at any distance, as in technical drawings. `width` is the size in world units of the
+/// area covered by the longer side of the canvas.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Orthographic {
+    width: f64,
+}
+
+impl Orthographic {
+    pub fn new(width: f64) -> Self {
+        Self { width }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+}
+
+impl Projection for Orthographic {
+    fn ray(&self, u: f64, v: f64, aspect: f64) -> Option<(Point, Vector)> {
+        let (x, y) = screen_coordinates(u, v, aspect);
+        let half_width = self.width / 2.0;
+
+        Some((
+            Point::new(x * half_width, y * half_width, 0.0),
+            Vector::new(0.0, 0.0, -1.0),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::projection::Projection;
+    use crate::projection::orthographic::Orthographic;
+    use crate::vector::{Point, Vector};
+
+    #[test]
+    fn test_ray() {
+        let orthographic = Orthographic::new(4.0);
+
+        for (u, v, origin) in [
+            (0.5, 0.5, Point::new(0.0, 0.0, 0.0)),
+            (0.0, 0.0, Point::new(-2.0, 1.0, 0.0)),
+            (1.0, 1.0, Point::new(2.0, -1.0, 0.0)),
+        ] {
+            let (ray_origin, direction) = orthographic.ray(u, v, 2.0).unwrap();
+
+            origin.assert_approx_eq(&ray_origin);
+            Vector::new(0.0, 0.0, -1.0).assert_approx_eq(&direction);
+        }
+    }
+}
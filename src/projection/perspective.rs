@@ -0,0 +1,51 @@
+use crate::projection::{Projection, screen_coordinates};
+use crate::vector::{ORIGIN, Point, Vector};
+
+/// A pinhole projection, in which all rays start at the camera and parallel lines converge.
+/// The field of view, in radians, spans the longer side of the canvas.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Perspective {
+    field_of_view: f64,
+}
+
+impl Perspective {
+    pub fn new(field_of_view: f64) -> Self {
+        Self { field_of_view }
+    }
+
+    pub fn field_of_view(&self) -> f64 {
+        self.field_of_view
+    }
+}
+
+impl Projection for Perspective {
+    fn ray(&self, u: f64, v: f64, aspect: f64) -> Option<(Point, Vector)> {
+        let (x, y) = screen_coordinates(u, v, aspect);
+        let half_view = (self.field_of_view / 2.0).tan();
+
+        Some((ORIGIN, Vector::new(x * half_view, y * half_view, -1.0)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::projection::Projection;
+    use crate::projection::perspective::Perspective;
+    use crate::vector::{Point, Vector};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_ray() {
+        let perspective = Perspective::new(PI / 2.0);
+
+        let (origin, direction) = perspective.ray(0.5, 0.5, 2.0).unwrap();
+
+        Point::new(0.0, 0.0, 0.0).assert_approx_eq(&origin);
+        Vector::new(0.0, 0.0, -1.0).assert_approx_eq(&direction);
+
+        // A 90° field of view reaches 45° to either side
+        let (_, direction) = perspective.ray(0.0, 0.0, 2.0).unwrap();
+
+        Vector::new(-1.0, 0.5, -1.0).assert_approx_eq_epsilon(&direction, 1e-12);
+    }
+}
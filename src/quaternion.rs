@@ -0,0 +1,316 @@
+use crate::matrix::Matrix;
+use crate::vector::Vector;
+use std::ops::{Add, Mul, Neg};
+
+#[cfg(test)]
+use assert_float_eq::assert_float_absolute_eq;
+
+/// How close the dot product of two unit quaternions must be to 1 before `slerp` falls back to
+/// linear interpolation, which avoids dividing by the sine of a vanishingly small angle.
+const SLERP_EPSILON: f64 = 1e-9;
+
+pub const IDENTITY: Quaternion = Quaternion {
+    components: [1.0, 0.0, 0.0, 0.0],
+};
+
+/// A quaternion `w + xi + yj + zk`. Unit quaternions represent rotations in three dimensions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Quaternion {
+    components: [f64; 4],
+}
+
+impl Quaternion {
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self {
+            components: [w, x, y, z],
+        }
+    }
+
+    /// Returns the unit quaternion that rotates `angle` radians about `axis`, counterclockwise
+    /// when looking back down the axis toward the origin.
+    pub fn from_axis_angle(axis: &Vector, angle: f64) -> Self {
+        let [x, y, z, _] = axis.normalize().components();
+        let (sin, cos) = (angle / 2.0).sin_cos();
+
+        Self::new(cos, x * sin, y * sin, z * sin)
+    }
+
+    /// Returns the components of this quaternion in the order w, x, y, z.
+    pub fn components(&self) -> [f64; 4] {
+        self.components
+    }
+
+    pub fn dot(&self, rhs: &Self) -> f64 {
+        (0..4).map(|i| self.components[i] * rhs.components[i]).sum()
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        *self * (1.0 / self.magnitude())
+    }
+
+    pub fn conjugate(&self) -> Self {
+        let [w, x, y, z] = self.components;
+
+        Self::new(w, -x, -y, -z)
+    }
+
+    /// Rotates `vector` by this unit quaternion.
+    pub fn rotate(&self, vector: &Vector) -> Vector {
+        let [w, x, y, z] = self.components;
+        let axis = Vector::new(x, y, z);
+        let t = axis.cross(vector) * 2.0;
+
+        *vector + &(t * w) + &axis.cross(&t)
+    }
+
+    /// Spherically interpolates between this unit quaternion and `other`, turning at a constant
+    /// rate along the shorter way between the rotations they represent.
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        let mut other = *other;
+        let mut dot = self.dot(&other);
+
+        // q and -q represent the same rotation, so pick whichever is closer
+        if dot < 0.0 {
+            other = -other;
+            dot = -dot;
+        }
+
+        if dot > 1.0 - SLERP_EPSILON {
+            return ((*self * (1.0 - t)) + (other * t)).normalize();
+        }
+
+        let angle = dot.acos();
+        let sin = angle.sin();
+
+        (*self * (((1.0 - t) * angle).sin() / sin)) + (other * ((t * angle).sin() / sin))
+    }
+
+    #[cfg(test)]
+    pub fn assert_approx_eq_epsilon(&self, other: &Quaternion, epsilon: f64) {
+        for i in 0..4 {
+            assert_float_absolute_eq!(self.components[i], other.components[i], epsilon);
+        }
+    }
+}
+
+impl Add<Quaternion> for Quaternion {
+    type Output = Self;
+
+    fn add(self, rhs: Quaternion) -> Self::Output {
+        Self {
+            components: [0, 1, 2, 3].map(|i| self.components[i] + rhs.components[i]),
+        }
+    }
+}
+
+impl Mul<f64> for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self {
+            components: self.components.map(|component| component * rhs),
+        }
+    }
+}
+
+/// Composes two rotations, so that `a * b` rotates by `b` and then by `a`.
+impl Mul<Quaternion> for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Quaternion) -> Self::Output {
+        let [w1, x1, y1, z1] = self.components;
+        let [w2, x2, y2, z2] = rhs.components;
+
+        Self::new(
+            (w1 * w2) - (x1 * x2) - (y1 * y2) - (z1 * z2),
+            (w1 * x2) + (x1 * w2) + (y1 * z2) - (z1 * y2),
+            (w1 * y2) - (x1 * z2) + (y1 * w2) + (z1 * x2),
+            (w1 * z2) + (x1 * y2) - (y1 * x2) + (z1 * w2),
+        )
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1.0
+    }
+}
+
+/// Converts a rotation matrix, whose upper left 3×3 block must be orthonormal with a determinant of
+/// 1, to a unit quaternion (Shepperd's method, which divides by the largest available quantity
+/// to stay accurate).
+impl From<&Matrix<4>> for Quaternion {
+    fn from(matrix: &Matrix<4>) -> Self {
+        let m = |row, col| matrix[(row, col)];
+        let trace = m(0, 0) + m(1, 1) + m(2, 2);
+
+        let quaternion = if trace > 0.0 {
+            let s = 2.0 * (trace + 1.0).sqrt();
+
+            Self::new(
+                0.25 * s,
+                (m(2, 1) - m(1, 2)) / s,
+                (m(0, 2) - m(2, 0)) / s,
+                (m(1, 0) - m(0, 1)) / s,
+            )
+        } else if m(0, 0) > m(1, 1) && m(0, 0) > m(2, 2) {
+            let s = 2.0 * (1.0 + m(0, 0) - m(1, 1) - m(2, 2)).sqrt();
+
+            Self::new(
+                (m(2, 1) - m(1, 2)) / s,
+                0.25 * s,
+                (m(0, 1) + m(1, 0)) / s,
+                (m(0, 2) + m(2, 0)) / s,
+            )
+        } else if m(1, 1) > m(2, 2) {
+            let s = 2.0 * (1.0 + m(1, 1) - m(0, 0) - m(2, 2)).sqrt();
+
+            Self::new(
+                (m(0, 2) - m(2, 0)) / s,
+                (m(0, 1) + m(1, 0)) / s,
+                0.25 * s,
+                (m(1, 2) + m(2, 1)) / s,
+            )
+        } else {
+            let s = 2.0 * (1.0 + m(2, 2) - m(0, 0) - m(1, 1)).sqrt();
+
+            Self::new(
+                (m(1, 0) - m(0, 1)) / s,
+                (m(0, 2) + m(2, 0)) / s,
+                (m(1, 2) + m(2, 1)) / s,
+                0.25 * s,
+            )
+        };
+
+        quaternion.normalize()
+    }
+}
+
+/// Converts a unit quaternion to the rotation matrix it represents.
+impl From<&Quaternion> for Matrix<4> {
+    fn from(quaternion: &Quaternion) -> Self {
+        let [w, x, y, z] = quaternion.components;
+
+        Matrix::new([
+            [
+                1.0 - (2.0 * ((y * y) + (z * z))),
+                2.0 * ((x * y) - (w * z)),
+                2.0 * ((x * z) + (w * y)),
+                0.0,
+            ],
+            [
+                2.0 * ((x * y) + (w * z)),
+                1.0 - (2.0 * ((x * x) + (z * z))),
+                2.0 * ((y * z) - (w * x)),
+                0.0,
+            ],
+            [
+                2.0 * ((x * z) - (w * y)),
+                2.0 * ((y * z) + (w * x)),
+                1.0 - (2.0 * ((x * x) + (y * y))),
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::matrix::Matrix;
+    use crate::quaternion::{IDENTITY, Quaternion};
+    use crate::transform::{Transformation, transform};
+    use crate::vector::{Point, Vector};
+    use assert_float_eq::assert_float_absolute_eq;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_from_axis_angle() {
+        let quaternion = Quaternion::from_axis_angle(&Vector::new(0.0, 0.0, 2.0), PI / 2.0);
+
+        assert_float_absolute_eq!(1.0, quaternion.magnitude(), 1e-12);
+
+        Vector::new(0.0, 1.0, 0.0)
+            .assert_approx_eq_epsilon(&quaternion.rotate(&Vector::new(1.0, 0.0, 0.0)), 1e-12);
+
+        // Rotations about the principal axes match the matrices for those rotations
+        for (axis, transformation) in [
+            (Vector::new(1.0, 0.0, 0.0), Transformation::RotateX(0.7)),
+            (Vector::new(0.0, 1.0, 0.0), Transformation::RotateY(0.7)),
+            (Vector::new(0.0, 0.0, 1.0), Transformation::RotateZ(0.7)),
+        ] {
+            let matrix = Matrix::from(&Quaternion::from_axis_angle(&axis, 0.7));
+            let point = Point::new(1.0, 2.0, 3.0);
+
+            (transform(&[transformation]) * &point)
+                .assert_approx_eq_epsilon(&(matrix * &point), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_from_matrix() {
+        // Cover each branch of Shepperd's method, including half turns
+        for (axis, angle) in [
+            (Vector::new(1.0, 2.0, 3.0), 0.5),
+            (Vector::new(1.0, 0.1, 0.0), PI),
+            (Vector::new(0.1, 1.0, 0.0), PI),
+            (Vector::new(0.0, 0.1, 1.0), PI),
+        ] {
+            let quaternion = Quaternion::from_axis_angle(&axis, angle);
+            let converted = Quaternion::from(&Matrix::from(&quaternion));
+
+            // q and -q are the same rotation
+            let sign = converted.dot(&quaternion).signum();
+
+            (converted * sign).assert_approx_eq_epsilon(&quaternion, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_compose() {
+        let x = Quaternion::from_axis_angle(&Vector::new(1.0, 0.0, 0.0), PI / 2.0);
+        let y = Quaternion::from_axis_angle(&Vector::new(0.0, 1.0, 0.0), PI / 2.0);
+        let vector = Vector::new(0.0, 0.0, 1.0);
+
+        // Rotating by x and then by y matches the product y * x
+        y.rotate(&x.rotate(&vector))
+            .assert_approx_eq_epsilon(&(y * x).rotate(&vector), 1e-12);
+
+        (y * x).assert_approx_eq_epsilon(&(y * x * IDENTITY), 1e-12);
+        (x * x.conjugate()).assert_approx_eq_epsilon(&IDENTITY, 1e-12);
+    }
+
+    #[test]
+    fn test_slerp() {
+        let axis = Vector::new(1.0, 1.0, 0.0);
+        let start = Quaternion::from_axis_angle(&axis, 0.2);
+        let end = Quaternion::from_axis_angle(&axis, 1.4);
+
+        start
+            .slerp(&end, 0.0)
+            .assert_approx_eq_epsilon(&start, 1e-12);
+        start.slerp(&end, 1.0).assert_approx_eq_epsilon(&end, 1e-12);
+
+        start
+            .slerp(&end, 0.25)
+            .assert_approx_eq_epsilon(&Quaternion::from_axis_angle(&axis, 0.5), 1e-12);
+
+        // Slerp takes the shorter way around, even between quaternions in opposite hemispheres
+        let near = Quaternion::from_axis_angle(&axis, 0.1);
+        let far = -Quaternion::from_axis_angle(&axis, -0.1);
+        let halfway = near.slerp(&far, 0.5);
+
+        halfway
+            .rotate(&Vector::new(0.0, 0.0, 1.0))
+            .assert_approx_eq_epsilon(&Vector::new(0.0, 0.0, 1.0), 1e-12);
+
+        // Nearly identical rotations still interpolate to a unit quaternion
+        assert_float_absolute_eq!(1.0, IDENTITY.slerp(&IDENTITY, 0.5).magnitude(), 1e-12);
+    }
+}
@@ -3,14 +3,27 @@ use crate::matrix::Matrix;
 use crate::vector::{Point, Vector};
 use std::ops::Mul;
 
+#[derive(Clone)]
 pub struct Ray {
     origin: Point,
     direction: Vector,
+    time: f64,
 }
 
 impl Ray {
     pub fn new(origin: Point, direction: Vector) -> Self {
-        Self { origin, direction }
+        Self {
+            origin,
+            direction,
+            time: 0.0,
+        }
+    }
+
+    /// Sets the moment at which this ray samples the scene, which determines where moving
+    /// shapes are.
+    pub fn with_time(mut self, time: f64) -> Self {
+        self.time = time;
+        self
     }
 
     pub fn origin(&self) -> &Point {
@@ -21,6 +34,10 @@ impl Ray {
         &self.direction
     }
 
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
     pub fn position(&self, distance: f64) -> Point {
         self.origin + &(self.direction * distance)
     }
@@ -41,12 +58,13 @@ impl Mul<&Ray> for &Matrix<4> {
         Ray {
             origin: self * &rhs.origin,
             direction: self * &rhs.direction,
+            time: rhs.time,
         }
     }
 }
 
 pub trait IntersectRay {
-    fn intersect(&self, ray: &Ray) -> Vec<Intersection>;
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>>;
 }
 
 #[cfg(test)]
@@ -75,5 +93,13 @@ mod test {
             ray.origin.assert_approx_eq(&Point::new(2.0, 6.0, 12.0));
             ray.direction.assert_approx_eq(&Vector::new(0.0, 3.0, 0.0));
         }
+
+        {
+            let transformation = transform::transform(&[Transformation::Translate(3.0, 4.0, 5.0)]);
+            let ray = transformation
+                * &Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0)).with_time(0.5);
+
+            assert_eq!(0.5, ray.time());
+        }
     }
 }
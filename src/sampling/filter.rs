@@ -0,0 +1,113 @@
+/// Reconstruction filters that weight each sample's contribution to nearby pixels according to
+/// its distance from their centers. All filters are separable.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Filter {
+    /// Weights every sample within the pixel equally.
+    Box,
+    /// Weights samples linearly by distance, reaching zero one pixel away.
+    Tent,
+    /// A Gaussian with falloff `alpha`, shifted so that it reaches zero at `radius`.
+    Gaussian { radius: f64, alpha: f64 },
+    /// The Mitchell-Netravali cubic family with parameters `b` and `c`; `b = c = 1/3` is the
+    /// authors' recommended compromise between blurring and ringing.
+    MitchellNetravali { b: f64, c: f64 },
+}
+
+impl Filter {
+    /// Returns the distance, in pixels, beyond which this filter's weight is zero.
+    pub fn radius(&self) -> f64 {
+        match self {
+            Filter::Box => 0.5,
+            Filter::Tent => 1.0,
+            Filter::Gaussian { radius, .. } => *radius,
+            Filter::MitchellNetravali { .. } => 2.0,
+        }
+    }
+
+    /// Returns the weight of a sample offset by (`dx`, `dy`) pixels from a pixel's center. Some
+    /// filters have negative lobes.
+    pub fn weight(&self, dx: f64, dy: f64) -> f64 {
+        self.weight_1d(dx) * self.weight_1d(dy)
+    }
+
+    fn weight_1d(&self, d: f64) -> f64 {
+        let d = d.abs();
+
+        if d > self.radius() {
+            return 0.0;
+        }
+
+        match self {
+            Filter::Box => 1.0,
+            Filter::Tent => 1.0 - d,
+            Filter::Gaussian { radius, alpha } => {
+                ((-alpha * d * d).exp() - (-alpha * radius * radius).exp()).max(0.0)
+            }
+            Filter::MitchellNetravali { b, c } => {
+                // The cubic is piecewise, with one segment inside one pixel and another out to two
+                let (b, c) = (*b, *c);
+
+                if d < 1.0 {
+                    ((12.0 - 9.0 * b - 6.0 * c) * d * d * d
+                        + (-18.0 + 12.0 * b + 6.0 * c) * d * d
+                        + (6.0 - 2.0 * b))
+                        / 6.0
+                } else {
+                    ((-b - 6.0 * c) * d * d * d
+                        + (6.0 * b + 30.0 * c) * d * d
+                        + (-12.0 * b - 48.0 * c) * d
+                        + (8.0 * b + 24.0 * c))
+                        / 6.0
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sampling::filter::Filter;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+
+    #[test]
+    fn test_box() {
+        assert_f64_near!(1.0, Filter::Box.weight(0.0, 0.0));
+        assert_f64_near!(1.0, Filter::Box.weight(0.49, -0.49));
+        assert_f64_near!(0.0, Filter::Box.weight(0.51, 0.0));
+    }
+
+    #[test]
+    fn test_tent() {
+        assert_f64_near!(1.0, Filter::Tent.weight(0.0, 0.0));
+        assert_f64_near!(0.25, Filter::Tent.weight(0.5, -0.5));
+        assert_f64_near!(0.0, Filter::Tent.weight(1.0, 0.0));
+        assert_f64_near!(0.0, Filter::Tent.weight(0.0, 1.5));
+    }
+
+    #[test]
+    fn test_gaussian() {
+        let filter = Filter::Gaussian {
+            radius: 1.5,
+            alpha: 2.0,
+        };
+
+        assert_f64_near!(1.0 - (-4.5f64).exp(), filter.weight_1d(0.0));
+        assert_float_absolute_eq!(0.0, filter.weight(1.5, 0.0), 1e-12);
+        assert!(filter.weight(0.5, 0.0) < filter.weight(0.25, 0.0));
+    }
+
+    #[test]
+    fn test_mitchell_netravali() {
+        let filter = Filter::MitchellNetravali {
+            b: 1.0 / 3.0,
+            c: 1.0 / 3.0,
+        };
+
+        assert_f64_near!(8.0 / 9.0, filter.weight_1d(0.0));
+        assert_float_absolute_eq!(1.0 / 18.0, filter.weight_1d(1.0), 1e-12);
+        assert_float_absolute_eq!(0.0, filter.weight_1d(2.0), 1e-12);
+
+        // Negative lobe between one and two pixels from the center
+        assert!(filter.weight_1d(1.5) < 0.0);
+    }
+}
@@ -0,0 +1,447 @@
+pub mod filter;
+pub mod pattern;
+pub mod warp;
+
+use crate::canvas::Canvas;
+use crate::color;
+use crate::color::Color;
+use crate::random::Random;
+use crate::sampling::filter::Filter;
+use crate::sampling::pattern::SamplePattern;
+use std::ops::Range;
+
+/// Shoots multiple samples through each pixel of a canvas and reconstructs pixel values from
+/// them with a filter.
+#[derive(Copy, Clone, Debug)]
+pub struct Sampler {
+    samples: usize,
+    pattern: SamplePattern,
+    filter: Filter,
+    seed: u64,
+    adaptive: Option<AdaptiveSampling>,
+}
+
+/// Keeps adding batches of samples to a pixel until the estimated variance of its mean luminance
+/// drops to `variance_threshold` or the pixel has received `max_samples` samples.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AdaptiveSampling {
+    max_samples: usize,
+    variance_threshold: f64,
+}
+
+impl AdaptiveSampling {
+    pub fn new(max_samples: usize, variance_threshold: f64) -> Self {
+        Self {
+            max_samples,
+            variance_threshold,
+        }
+    }
+
+    pub fn max_samples(&self) -> usize {
+        self.max_samples
+    }
+
+    pub fn variance_threshold(&self) -> f64 {
+        self.variance_threshold
+    }
+}
+
+impl Sampler {
+    pub fn new(samples: usize, pattern: SamplePattern, filter: Filter, seed: u64) -> Self {
+        Self {
+            samples,
+            pattern,
+            filter,
+            seed,
+            adaptive: None,
+        }
+    }
+
+    /// Treats this sampler's sample count as the first of potentially many batches of samples
+    /// for each pixel.
+    pub fn with_adaptive_sampling(mut self, adaptive: AdaptiveSampling) -> Self {
+        self.adaptive = Some(adaptive);
+        self
+    }
+
+    /// Returns the number of samples in each batch (and, without adaptive sampling, the number of
+    /// samples taken for every pixel).
+    pub fn samples_per_pixel(&self) -> usize {
+        self.pattern.sample_count(self.samples)
+    }
+
+    pub fn pattern(&self) -> &SamplePattern {
+        &self.pattern
+    }
+
+    pub fn filter(&self) -> &Filter {
+        &self.filter
+    }
+
+    pub fn adaptive_sampling(&self) -> Option<&AdaptiveSampling> {
+        self.adaptive.as_ref()
+    }
+
+    /// Fills `canvas` by calling `trace` for each sample. `trace` receives the sample's position
+    /// in continuous image coordinates, where pixel (x, y) covers [x, x + 1) × [y, y + 1), and
+    /// returns the color seen along that sample's ray or `None` if the ray hit nothing. Each
+    /// pixel's coverage is the filtered fraction of samples that hit something.
+    ///
+    /// Returns the number of samples taken within each pixel.
+    pub fn render<F>(&self, canvas: &mut Canvas, mut trace: F) -> SampleCounts
+    where
+        F: FnMut(f64, f64) -> Option<Color>,
+    {
+        let (width, height) = (canvas.width(), canvas.height());
+
+        let mut colors = vec![color::BLACK; width * height];
+        let mut coverage = vec![0.0; width * height];
+        let mut weights = vec![0.0; width * height];
+        let mut counts = vec![0; width * height];
+
+        for y in 0..height {
+            for x in 0..width {
+                // Give each pixel its own stream so results don't depend on traversal order
+                let mut random = Random::for_stream(self.seed, ((y * width) + x) as u64);
+                let mut sequence = self.pattern.sequence(&mut random);
+                let mut statistics = LuminanceStatistics::default();
+
+                loop {
+                    for (dx, dy) in sequence.next_batch(self.samples) {
+                        let (sample_x, sample_y) = (x as f64 + dx, y as f64 + dy);
+                        let sample = trace(sample_x, sample_y);
+
+                        statistics.add(sample.as_ref().unwrap_or(&color::BLACK));
+                        self.splat(
+                            sample_x,
+                            sample_y,
+                            sample,
+                            canvas,
+                            &mut colors,
+                            &mut coverage,
+                            &mut weights,
+                        );
+                    }
+
+                    let done = match &self.adaptive {
+                        None => true,
+                        Some(adaptive) => {
+                            statistics.count >= adaptive.max_samples
+                                || statistics.variance_of_mean() <= adaptive.variance_threshold
+                        }
+                    };
+
+                    if done {
+                        break;
+                    }
+                }
+
+                counts[(y * width) + x] = statistics.count;
+            }
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let index = (y * width) + x;
+
+                if weights[index].abs() > f64::EPSILON {
+                    canvas.set_pixel(x, y, colors[index] * (1.0 / weights[index]));
+                    canvas.set_coverage(x, y, coverage[index] / weights[index]);
+                } else {
+                    canvas.set_pixel(x, y, color::BLACK);
+                    canvas.set_coverage(x, y, 0.0);
+                }
+            }
+        }
+
+        SampleCounts { width, counts }
+    }
+
+    /// Adds a sample's weighted contribution to every pixel whose center is within the filter's
+    /// radius of the sample.
+    #[allow(clippy::too_many_arguments)]
+    fn splat(
+        &self,
+        sample_x: f64,
+        sample_y: f64,
+        sample: Option<Color>,
+        canvas: &Canvas,
+        colors: &mut [Color],
+        coverage: &mut [f64],
+        weights: &mut [f64],
+    ) {
+        let (color, hit) = match sample {
+            Some(color) => (color, 1.0),
+            None => (color::BLACK, 0.0),
+        };
+
+        let radius = self.filter.radius();
+
+        for pixel_y in Self::pixel_range(sample_y, radius, canvas.height()) {
+            for pixel_x in Self::pixel_range(sample_x, radius, canvas.width()) {
+                let weight = self.filter.weight(
+                    sample_x - (pixel_x as f64 + 0.5),
+                    sample_y - (pixel_y as f64 + 0.5),
+                );
+
+                let index = (pixel_y * canvas.width()) + pixel_x;
+
+                colors[index] = colors[index] + (color * weight);
+                coverage[index] += hit * weight;
+                weights[index] += weight;
+            }
+        }
+    }
+
+    /// Returns the range of pixel indices whose centers lie within `radius` of `position`.
+    fn pixel_range(position: f64, radius: f64, limit: usize) -> Range<usize> {
+        let first = (position - 0.5 - radius).ceil().max(0.0) as usize;
+        let last = (position - 0.5 + radius).floor();
+
+        if last < 0.0 {
+            return 0..0;
+        }
+
+        first..(last as usize + 1).min(limit)
+    }
+}
+
+/// Running totals used to estimate the variance of a pixel's mean luminance.
+#[derive(Default)]
+struct LuminanceStatistics {
+    count: usize,
+    sum: f64,
+    sum_of_squares: f64,
+}
+
+impl LuminanceStatistics {
+    fn add(&mut self, color: &Color) {
+        let luminance = color.luminance();
+
+        self.count += 1;
+        self.sum += luminance;
+        self.sum_of_squares += luminance * luminance;
+    }
+
+    fn variance_of_mean(&self) -> f64 {
+        if self.count < 2 {
+            return f64::INFINITY;
+        }
+
+        let n = self.count as f64;
+        let mean = self.sum / n;
+        let sample_variance = ((self.sum_of_squares / n) - (mean * mean)).max(0.0) * n / (n - 1.0);
+
+        sample_variance / n
+    }
+}
+
+/// The number of samples taken within each pixel of a rendered canvas.
+pub struct SampleCounts {
+    width: usize,
+    counts: Vec<usize>,
+}
+
+impl SampleCounts {
+    pub fn get(&self, x: usize, y: usize) -> usize {
+        self.counts[(y * self.width) + x]
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.total() as f64 / self.counts.len() as f64
+    }
+
+    /// Renders sample counts as a heatmap running from blue (the fewest samples) through green
+    /// and yellow to red (the most samples).
+    pub fn heatmap(&self) -> Canvas {
+        const RAMP: [Color; 4] = [
+            Color::new(0.0, 0.0, 1.0),
+            Color::new(0.0, 1.0, 0.0),
+            Color::new(1.0, 1.0, 0.0),
+            Color::new(1.0, 0.0, 0.0),
+        ];
+
+        let min = self.counts.iter().copied().min().unwrap_or(0);
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+
+        let mut canvas = Canvas::new(self.width, self.counts.len() / self.width);
+
+        for (index, &count) in self.counts.iter().enumerate() {
+            let t = if max > min {
+                (count - min) as f64 / (max - min) as f64
+            } else {
+                0.0
+            };
+
+            let position = t * (RAMP.len() - 1) as f64;
+            let segment = (position as usize).min(RAMP.len() - 2);
+            let fraction = position - segment as f64;
+
+            canvas.set_pixel(
+                index % self.width,
+                index / self.width,
+                (RAMP[segment] * (1.0 - fraction)) + (RAMP[segment + 1] * fraction),
+            );
+        }
+
+        canvas
+    }
+}
+
+impl Default for Sampler {
+    /// A single sample through the center of each pixel.
+    fn default() -> Self {
+        Sampler::new(1, SamplePattern::Grid, Filter::Box, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::canvas::Canvas;
+    use crate::color;
+    use crate::color::Color;
+    use crate::random::Random;
+    use crate::sampling::filter::Filter;
+    use crate::sampling::pattern::SamplePattern;
+    use crate::sampling::{AdaptiveSampling, SampleCounts, Sampler};
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+
+    #[test]
+    fn test_pixel_range() {
+        assert_eq!(1..2, Sampler::pixel_range(1.25, 0.5, 4));
+        assert_eq!(0..3, Sampler::pixel_range(1.25, 1.5, 4));
+        assert_eq!(3..4, Sampler::pixel_range(3.9, 1.0, 4));
+        assert_eq!(0..1, Sampler::pixel_range(0.1, 0.5, 4));
+    }
+
+    #[test]
+    fn test_render_uniform() {
+        let color = Color::new(0.25, 0.5, 0.75);
+
+        for pattern in [
+            SamplePattern::Grid,
+            SamplePattern::Jittered,
+            SamplePattern::Halton,
+            SamplePattern::Sobol,
+        ] {
+            for filter in [
+                Filter::Box,
+                Filter::Tent,
+                Filter::Gaussian {
+                    radius: 1.5,
+                    alpha: 2.0,
+                },
+                Filter::MitchellNetravali {
+                    b: 1.0 / 3.0,
+                    c: 1.0 / 3.0,
+                },
+            ] {
+                let mut canvas = Canvas::new(4, 3);
+                Sampler::new(8, pattern, filter, 0).render(&mut canvas, |_, _| Some(color));
+
+                for y in 0..3 {
+                    for x in 0..4 {
+                        color.assert_approx_eq_epsilon(canvas.get_pixel(x, y), 1e-12);
+                        assert_float_absolute_eq!(1.0, canvas.get_coverage(x, y), 1e-12);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_coverage() {
+        let mut canvas = Canvas::new(4, 1);
+
+        Sampler::new(4, SamplePattern::Grid, Filter::Box, 0).render(&mut canvas, |x, _| {
+            if x < 1.5 { Some(color::WHITE) } else { None }
+        });
+
+        assert_float_absolute_eq!(1.0, canvas.get_coverage(0, 0), 1e-12);
+        assert_float_absolute_eq!(0.5, canvas.get_coverage(1, 0), 1e-12);
+        assert_float_absolute_eq!(0.0, canvas.get_coverage(2, 0), 1e-12);
+        assert_float_absolute_eq!(0.0, canvas.get_coverage(3, 0), 1e-12);
+
+        // Pixel colors are premultiplied by coverage
+        Color::new(0.5, 0.5, 0.5).assert_approx_eq(canvas.get_pixel(1, 0));
+    }
+
+    #[test]
+    fn test_render_adaptive() {
+        let mut canvas = Canvas::new(4, 1);
+
+        let sampler = Sampler::new(4, SamplePattern::Jittered, Filter::Box, 0)
+            .with_adaptive_sampling(AdaptiveSampling::new(64, 1e-4));
+
+        // The left half of the image is flat; the right half is noisy
+        let mut random = Random::new(0);
+
+        let counts = sampler.render(&mut canvas, |x, _| {
+            if x < 2.0 {
+                Some(color::WHITE)
+            } else {
+                Some(color::WHITE * random.next_f64())
+            }
+        });
+
+        assert_eq!(4, counts.get(0, 0));
+        assert_eq!(4, counts.get(1, 0));
+        assert!(counts.get(2, 0) > 4);
+        assert!(counts.get(3, 0) <= 64);
+
+        color::WHITE.assert_approx_eq(canvas.get_pixel(0, 0));
+        Color::new(0.5, 0.5, 0.5).assert_approx_eq_epsilon(canvas.get_pixel(3, 0), 0.1);
+    }
+
+    #[test]
+    fn test_render_counts() {
+        let mut canvas = Canvas::new(3, 2);
+
+        let counts = Sampler::new(9, SamplePattern::Grid, Filter::Box, 0)
+            .render(&mut canvas, |_, _| Some(color::WHITE));
+
+        assert_eq!(9, counts.get(2, 1));
+        assert_eq!(54, counts.total());
+        assert_f64_near!(9.0, counts.mean());
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let counts = SampleCounts {
+            width: 3,
+            counts: vec![4, 10, 16],
+        };
+
+        let heatmap = counts.heatmap();
+
+        Color::new(0.0, 0.0, 1.0).assert_approx_eq(heatmap.get_pixel(0, 0));
+        Color::new(0.5, 1.0, 0.0).assert_approx_eq(heatmap.get_pixel(1, 0));
+        Color::new(1.0, 0.0, 0.0).assert_approx_eq(heatmap.get_pixel(2, 0));
+    }
+
+    #[test]
+    fn test_render_reproducible() {
+        let render = |seed| {
+            let mut canvas = Canvas::new(4, 4);
+
+            Sampler::new(4, SamplePattern::Jittered, Filter::Tent, seed)
+                .render(&mut canvas, |x, y| Some(Color::new(x, y, 0.0)));
+
+            canvas
+        };
+
+        let (first, second, third) = (render(7), render(7), render(8));
+
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(first.get_pixel(x, y), second.get_pixel(x, y));
+            }
+        }
+
+        assert_ne!(first.get_pixel(1, 1), third.get_pixel(1, 1));
+    }
+}
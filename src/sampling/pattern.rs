@@ -0,0 +1,272 @@
+use crate::random::Random;
+
+/// Strategies for distributing sample positions within a pixel.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SamplePattern {
+    /// Samples at the centers of a regular grid of sub-pixel cells.
+    Grid,
+    /// One sample at a random position within each cell of a regular grid.
+    Jittered,
+    /// Points from the Halton sequence (bases 2 and 3), randomly shifted for each pixel.
+    Halton,
+    /// Points from the two-dimensional Sobol sequence, randomly scrambled for each pixel.
+    Sobol,
+}
+
+impl SamplePattern {
+    /// Returns the number of samples this pattern actually generates when asked for `samples`.
+    /// Grid-based patterns round up to the next perfect square.
+    pub fn sample_count(&self, samples: usize) -> usize {
+        match self {
+            SamplePattern::Grid | SamplePattern::Jittered => {
+                let per_axis = Self::samples_per_axis(samples);
+                per_axis * per_axis
+            }
+            SamplePattern::Halton | SamplePattern::Sobol => samples.max(1),
+        }
+    }
+
+    /// Generates sample positions as offsets in [0, 1) from a pixel's upper-left corner.
+    pub fn offsets(&self, samples: usize, random: &mut Random) -> Vec<(f64, f64)> {
+        self.sequence(random).next_batch(samples)
+    }
+
+    /// Starts a sequence of sample batches for a single pixel. Later batches continue
+    /// low-discrepancy sequences where earlier batches left off.
+    pub fn sequence(&self, random: &mut Random) -> SampleSequence {
+        let (shift, scramble) = match self {
+            SamplePattern::Halton => ((random.next_f64(), random.next_f64()), (0, 0)),
+            SamplePattern::Sobol => ((0.0, 0.0), (random.next_u32(), random.next_u32())),
+            SamplePattern::Grid | SamplePattern::Jittered => ((0.0, 0.0), (0, 0)),
+        };
+
+        let random = Random::new(random.next_u64());
+
+        SampleSequence {
+            pattern: *self,
+            random,
+            shift,
+            scramble,
+            next_index: 0,
+            batches: 0,
+        }
+    }
+
+    fn samples_per_axis(samples: usize) -> usize {
+        ((samples.max(1) as f64).sqrt().ceil()) as usize
+    }
+
+    fn grid<F: FnMut() -> f64>(samples: usize, mut offset: F) -> Vec<(f64, f64)> {
+        let per_axis = Self::samples_per_axis(samples);
+        let cell_size = 1.0 / per_axis as f64;
+
+        let mut offsets = Vec::with_capacity(per_axis * per_axis);
+
+        for y in 0..per_axis {
+            for x in 0..per_axis {
+                offsets.push((
+                    (x as f64 + offset()) * cell_size,
+                    (y as f64 + offset()) * cell_size,
+                ));
+            }
+        }
+
+        offsets
+    }
+}
+
+/// The sample positions for a single pixel, generated in batches.
+#[derive(Clone, Debug)]
+pub struct SampleSequence {
+    pattern: SamplePattern,
+    random: Random,
+    shift: (f64, f64),
+    scramble: (u32, u32),
+    next_index: usize,
+    batches: usize,
+}
+
+impl SampleSequence {
+    /// Returns the next batch of sample positions as offsets in [0, 1) from the pixel's
+    /// upper-left corner. Regular grids are jittered after the first batch, since repeating the
+    /// same positions would add no new information.
+    pub fn next_batch(&mut self, samples: usize) -> Vec<(f64, f64)> {
+        let samples = self.pattern.sample_count(samples);
+        let first = self.next_index;
+
+        self.next_index += samples;
+        self.batches += 1;
+
+        match self.pattern {
+            SamplePattern::Grid if self.batches == 1 => SamplePattern::grid(samples, || 0.5),
+            SamplePattern::Grid | SamplePattern::Jittered => {
+                SamplePattern::grid(samples, || self.random.next_f64())
+            }
+            SamplePattern::Halton => (first + 1..=first + samples)
+                .map(|i| {
+                    (
+                        (radical_inverse(i as u64, 2) + self.shift.0).fract(),
+                        (radical_inverse(i as u64, 3) + self.shift.1).fract(),
+                    )
+                })
+                .collect(),
+            SamplePattern::Sobol => (first as u32..(first + samples) as u32)
+                .map(|i| {
+                    (
+                        to_unit(i.reverse_bits() ^ self.scramble.0),
+                        to_unit(sobol_second_dimension(i) ^ self.scramble.1),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Mirrors the base-`base` digits of `index` about the radix point.
+fn radical_inverse(mut index: u64, base: u64) -> f64 {
+    let inverse_base = 1.0 / base as f64;
+    let mut scale = inverse_base;
+    let mut result = 0.0;
+
+    while index > 0 {
+        result += (index % base) as f64 * scale;
+        index /= base;
+        scale *= inverse_base;
+    }
+
+    result
+}
+
+/// Computes the second dimension of the Sobol sequence, whose generator matrix is Pascal's
+/// triangle modulo two.
+fn sobol_second_dimension(mut index: u32) -> u32 {
+    let mut direction = 1u32 << 31;
+    let mut result = 0;
+
+    while index != 0 {
+        if index & 1 != 0 {
+            result ^= direction;
+        }
+
+        index >>= 1;
+        direction ^= direction >> 1;
+    }
+
+    result
+}
+
+fn to_unit(value: u32) -> f64 {
+    value as f64 / (1u64 << 32) as f64
+}
+
+#[cfg(test)]
+mod test {
+    use crate::random::Random;
+    use crate::sampling::pattern::{
+        SamplePattern, radical_inverse, sobol_second_dimension, to_unit,
+    };
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_grid() {
+        let offsets = SamplePattern::Grid.offsets(4, &mut Random::new(0));
+
+        assert_eq!(
+            vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)],
+            offsets
+        );
+
+        assert_eq!(
+            vec![(0.5, 0.5)],
+            SamplePattern::Grid.offsets(1, &mut Random::new(0))
+        );
+        assert_eq!(9, SamplePattern::Grid.sample_count(5));
+        assert_eq!(9, SamplePattern::Grid.offsets(5, &mut Random::new(0)).len());
+    }
+
+    #[test]
+    fn test_jittered() {
+        let offsets = SamplePattern::Jittered.offsets(16, &mut Random::new(0));
+
+        assert_eq!(16, offsets.len());
+
+        // Each sample should land in its own cell of a 4x4 grid
+        for (i, (x, y)) in offsets.iter().enumerate() {
+            assert_eq!(i % 4, (x * 4.0) as usize);
+            assert_eq!(i / 4, (y * 4.0) as usize);
+        }
+    }
+
+    #[test]
+    fn test_sequence_batches() {
+        let mut random = Random::new(0);
+
+        // Continuing a Sobol sequence should produce the same points as one large batch
+        let mut sequence = SamplePattern::Sobol.sequence(&mut random.clone());
+        let mut batched = sequence.next_batch(4);
+        batched.extend(sequence.next_batch(4));
+
+        assert_eq!(SamplePattern::Sobol.offsets(8, &mut random), batched);
+
+        // Grid batches after the first are jittered rather than repeated
+        let mut sequence = SamplePattern::Grid.sequence(&mut random);
+        let first = sequence.next_batch(4);
+        let second = sequence.next_batch(4);
+
+        assert_eq!(
+            vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)],
+            first
+        );
+        assert_ne!(first, second);
+
+        for (i, (x, y)) in second.iter().enumerate() {
+            assert_eq!(i % 2, (x * 2.0) as usize);
+            assert_eq!(i / 2, (y * 2.0) as usize);
+        }
+    }
+
+    #[test]
+    fn test_radical_inverse() {
+        assert_f64_near!(0.5, radical_inverse(1, 2));
+        assert_f64_near!(0.25, radical_inverse(2, 2));
+        assert_f64_near!(0.75, radical_inverse(3, 2));
+        assert_f64_near!(1.0 / 3.0, radical_inverse(1, 3));
+        assert_f64_near!(2.0 / 3.0, radical_inverse(2, 3));
+        assert_f64_near!(1.0 / 9.0, radical_inverse(3, 3));
+    }
+
+    #[test]
+    fn test_sobol() {
+        let expected = [0.0, 0.5, 0.75, 0.25, 0.625, 0.125, 0.375, 0.875];
+
+        for (i, expected) in expected.iter().enumerate() {
+            assert_f64_near!(*expected, to_unit(sobol_second_dimension(i as u32)));
+        }
+    }
+
+    #[test]
+    fn test_low_discrepancy_stratification() {
+        for pattern in [SamplePattern::Halton, SamplePattern::Sobol] {
+            let offsets = pattern.offsets(16, &mut Random::new(3));
+
+            assert_eq!(16, offsets.len());
+
+            for (x, y) in &offsets {
+                assert!((0.0..1.0).contains(x));
+                assert!((0.0..1.0).contains(y));
+            }
+
+            // Both sequences should spread samples evenly across the quadrants of the pixel
+            for quadrant in 0..4 {
+                let count = offsets
+                    .iter()
+                    .filter(|(x, y)| {
+                        (*x >= 0.5) == (quadrant & 1 != 0) && (*y >= 0.5) == (quadrant & 2 != 0)
+                    })
+                    .count();
+
+                assert!((3..=5).contains(&count));
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::random::Random;
+use crate::vector::Vector;
+use std::f64::consts::PI;
+
+/// Returns a random unit vector, with all directions equally likely.
+pub fn uniform_sample_sphere(random: &mut Random) -> Vector {
+    let z = 1.0 - (2.0 * random.next_f64());
+    let r = (1.0 - (z * z)).max(0.0).sqrt();
+    let (sin, cos) = (2.0 * PI * random.next_f64()).sin_cos();
+
+    Vector::new(r * cos, r * sin, z)
+}
+
+/// Returns a point chosen uniformly at random on the unit disc.
+pub fn uniform_sample_disc(random: &mut Random) -> (f64, f64) {
+    let radius = random.next_f64().sqrt();
+    let (sin, cos) = (2.0 * PI * random.next_f64()).sin_cos();
+
+    (radius * cos, radius * sin)
+}
+
+/// Returns a point chosen uniformly at random inside the regular polygon with the given number
+/// of sides inscribed in the unit circle, with its first vertex at `rotation` radians.
+pub fn uniform_sample_polygon(sides: usize, rotation: f64, random: &mut Random) -> (f64, f64) {
+    let sides = sides.max(3);
+
+    // Pick one of the triangles fanning out from the center, then a point inside it
+    let side = ((random.next_f64() * sides as f64) as usize).min(sides - 1);
+    let angle = 2.0 * PI / sides as f64;
+    let (sin_a, cos_a) = (rotation + (angle * side as f64)).sin_cos();
+    let (sin_b, cos_b) = (rotation + (angle * (side + 1) as f64)).sin_cos();
+
+    let (mut u, mut v) = (random.next_f64(), random.next_f64());
+
+    if u + v > 1.0 {
+        (u, v) = (1.0 - u, 1.0 - v);
+    }
+
+    ((u * cos_a) + (v * cos_b), (u * sin_a) + (v * sin_b))
+}
+
+/// Returns a random unit vector in the hemisphere around `normal`, chosen with probability
+/// proportional to the cosine of its angle with `normal`.
+pub fn cosine_sample_hemisphere(normal: &Vector, random: &mut Random) -> Vector {
+    // Pick a uniformly-distributed point on the unit disc and project it up onto the hemisphere
+    let (x, y) = uniform_sample_disc(random);
+    let z = (1.0 - (x * x) - (y * y)).max(0.0).sqrt();
+
+    let (tangent, bitangent) = orthonormal_basis(normal);
+
+    (tangent * x) + &(bitangent * y) + &(*normal * z)
+}
+
+/// Returns two unit vectors that, along with the unit vector `normal`, form an orthonormal basis
+/// (Duff et al., "Building an Orthonormal Basis, Revisited").
+pub fn orthonormal_basis(normal: &Vector) -> (Vector, Vector) {
+    let [x, y, z, _] = normal.components();
+
+    let sign = 1.0f64.copysign(z);
+    let a = -1.0 / (sign + z);
+    let b = x * y * a;
+
+    (
+        Vector::new(1.0 + (sign * x * x * a), sign * b, -sign * x),
+        Vector::new(b, sign + (y * y * a), -y),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::random::Random;
+    use crate::sampling::warp::{
+        cosine_sample_hemisphere, orthonormal_basis, uniform_sample_disc, uniform_sample_polygon,
+        uniform_sample_sphere,
+    };
+    use crate::vector::Vector;
+    use assert_float_eq::assert_float_absolute_eq;
+
+    #[test]
+    fn test_orthonormal_basis() {
+        for normal in [
+            Vector::new(0.0, 0.0, 1.0),
+            Vector::new(0.0, 0.0, -1.0),
+            Vector::new(1.0, 2.0, 3.0).normalize(),
+        ] {
+            let (tangent, bitangent) = orthonormal_basis(&normal);
+
+            assert_float_absolute_eq!(1.0, tangent.magnitude(), 1e-12);
+            assert_float_absolute_eq!(1.0, bitangent.magnitude(), 1e-12);
+            assert_float_absolute_eq!(0.0, tangent.dot(&normal), 1e-12);
+            assert_float_absolute_eq!(0.0, bitangent.dot(&normal), 1e-12);
+            assert_float_absolute_eq!(0.0, tangent.dot(&bitangent), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cosine_sample_hemisphere() {
+        let normal = Vector::new(0.0, 1.0, 0.0);
+        let mut random = Random::new(0);
+        let mut sum = 0.0;
+
+        for _ in 0..10_000 {
+            let direction = cosine_sample_hemisphere(&normal, &mut random);
+            let cos = direction.dot(&normal);
+
+            assert_float_absolute_eq!(1.0, direction.magnitude(), 1e-12);
+            assert!(cos >= 0.0);
+
+            sum += cos;
+        }
+
+        // The mean cosine of a cosine-weighted distribution is 2/3
+        assert_float_absolute_eq!(2.0 / 3.0, sum / 10_000.0, 0.01);
+    }
+
+    #[test]
+    fn test_uniform_sample_sphere() {
+        let mut random = Random::new(0);
+        let mut sum = Vector::new(0.0, 0.0, 0.0);
+
+        for _ in 0..10_000 {
+            let direction = uniform_sample_sphere(&mut random);
+
+            assert_float_absolute_eq!(1.0, direction.magnitude(), 1e-12);
+            sum = sum + &direction;
+        }
+
+        // Directions balance out on average
+        assert_float_absolute_eq!(0.0, (sum / 10_000.0).magnitude(), 0.02);
+    }
+
+    #[test]
+    fn test_uniform_sample_disc_and_polygon() {
+        let mut random = Random::new(0);
+
+        for _ in 0..1_000 {
+            let (x, y) = uniform_sample_disc(&mut random);
+
+            assert!((x * x) + (y * y) <= 1.0);
+        }
+
+        // Points in a square with its corners on the axes satisfy |x| + |y| <= 1
+        let mut quadrants = [0; 4];
+
+        for _ in 0..4_000 {
+            let (x, y) = uniform_sample_polygon(4, 0.0, &mut random);
+
+            assert!(x.abs() + y.abs() <= 1.0 + 1e-12);
+            quadrants[(usize::from(x < 0.0) * 2) + usize::from(y < 0.0)] += 1;
+        }
+
+        for count in quadrants {
+            assert!((900..=1100).contains(&count));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::animation::track::Track;
+use crate::material::Material;
+use crate::shape::triangle::Triangle;
+use crate::transform::Transformation;
+use crate::vector::Point;
+
+/// A collection of triangles that share vertices and a material. Meshes are added to a world
+/// triangle by triangle (see `World::add_mesh`).
+pub struct Mesh {
+    triangles: Vec<Triangle>,
+}
+
+impl Mesh {
+    /// Creates a mesh from a list of vertices and a list of faces, each of which holds the
+    /// indices of its three vertices.
+    pub fn new(vertices: &[Point], faces: &[[usize; 3]], material: Material) -> Self {
+        Mesh {
+            triangles: faces
+                .iter()
+                .map(|[a, b, c]| {
+                    Triangle::new(vertices[*a], vertices[*b], vertices[*c], material.clone())
+                })
+                .collect(),
+        }
+    }
+
+    /// Makes every triangle in this mesh move as the keyframes dictate, treating its vertices as
+    /// being in object space.
+    pub fn with_keyframes(self, keyframes: Track<Vec<Transformation>>) -> Self {
+        Mesh {
+            triangles: self
+                .triangles
+                .into_iter()
+                .map(|triangle| triangle.with_keyframes(keyframes.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn triangles(&self) -> &[Triangle] {
+        &self.triangles
+    }
+
+    pub fn into_triangles(self) -> Vec<Triangle> {
+        self.triangles
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::material::Material;
+    use crate::shape::Shape;
+    use crate::shape::mesh::Mesh;
+    use crate::vector::Point;
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_new() {
+        // A unit square split into two triangles
+        let mesh = Mesh::new(
+            &[
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+                Point::new(0.0, 1.0, 0.0),
+            ],
+            &[[0, 1, 2], [0, 2, 3]],
+            Material::default(),
+        );
+
+        assert_eq!(2, mesh.triangles().len());
+
+        Point::new(1.0, 1.0, 0.0).assert_approx_eq(mesh.triangles()[1].vertices()[1]);
+
+        let area: f64 = mesh
+            .triangles()
+            .iter()
+            .map(|triangle| triangle.area())
+            .sum();
+        assert_f64_near!(1.0, area);
+    }
+}
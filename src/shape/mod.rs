@@ -1,17 +1,41 @@
+pub mod mesh;
+pub mod placement;
+pub mod quad;
 pub mod sphere;
+pub mod triangle;
 
+use crate::affine::Affine;
 use crate::intersection::Intersection;
 use crate::material::Material;
-use crate::matrix::Matrix;
+use crate::random::Random;
 use crate::ray::Ray;
+use crate::shape::placement::Placement;
 use crate::vector::{Point, Vector};
 
 pub trait Shape {
-    fn inverse_transformation(&self) -> &Matrix<4>;
+    /// Returns where this shape sits in the world, and how it moves.
+    fn placement(&self) -> &Placement;
+
+    /// Returns the inverse of this shape's transformation. Moving shapes return the inverse of
+    /// their transformation at the start of their motion.
+    fn inverse_transformation(&self) -> &Affine {
+        self.placement().inverse_transformation()
+    }
 
     fn material(&self) -> &Material;
 
-    fn normal_at(&self, world_point: &Point) -> Vector;
+    /// Returns the surface normal at `world_point` on this shape as it is at the given time.
+    fn normal_at(&self, world_point: &Point, time: f64) -> Vector;
+
+    /// Intersects this shape, as it is at the ray's time, with the given ray.
+    fn intersect(&self, world_ray: &Ray) -> Vec<Intersection<'_>>;
+
+    /// Returns the surface area of this shape in world space, at the start of any motion. Emissive
+    /// shapes are chosen in proportion to their area, so an approximation will do; the density
+    /// returned by `sample_surface` accounts for the shape's size at the sampled time.
+    fn area(&self) -> f64;
 
-    fn intersect(&self, world_ray: &Ray) -> Vec<Intersection>;
+    /// Returns a point chosen at random on this shape's surface as it is at the given time, along
+    /// with the surface normal there and the probability density (per unit area) of choosing it.
+    fn sample_surface(&self, time: f64, random: &mut Random) -> (Point, Vector, f64);
 }
@@ -0,0 +1,269 @@
+use crate::affine::Affine;
+use crate::animation::track::Track;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::transform;
+use crate::transform::Transformation;
+use crate::vector::{Point, Vector};
+use std::borrow::Cow;
+
+/// Where a shape sits in the world: the transformation from the space its geometry is given in
+/// (object space) to world space, which either stays fixed or moves as keyframes dictate.
+pub struct Placement {
+    transformation: Affine,
+    inverse_transformation: Affine,
+    normal_matrix: Matrix<3>,
+    keyframes: Option<Track<Vec<Transformation>>>,
+    identity: bool,
+}
+
+impl Placement {
+    pub fn new(transformations: &[Transformation]) -> Self {
+        Self::with_matrix(&transform::transform(transformations), None)
+    }
+
+    /// Creates a placement that moves as its keyframes dictate, which blurs the shape in renders
+    /// whose camera shutter stays open while it moves. The shape rests where its first keyframe
+    /// puts it.
+    pub fn with_keyframes(keyframes: Track<Vec<Transformation>>) -> Self {
+        let transformation = transform::transform(&keyframes.value_at(keyframes.start()));
+
+        Self::with_matrix(&transformation, Some(keyframes))
+    }
+
+    /// Creates a placement with the given transformation at rest, caching its inverse and the
+    /// matrix that carries normals from object space to world space (the transpose of the
+    /// inverse).
+    fn with_matrix(
+        transformation: &Matrix<4>,
+        keyframes: Option<Track<Vec<Transformation>>>,
+    ) -> Self {
+        let transformation = Affine::from(transformation);
+        let inverse_transformation = transformation.inverse().unwrap();
+
+        Placement {
+            normal_matrix: inverse_transformation.linear().transpose(),
+            identity: keyframes.is_none() && transformation == Affine::identity(),
+            inverse_transformation,
+            transformation,
+            keyframes,
+        }
+    }
+
+    pub fn keyframes(&self) -> Option<&Track<Vec<Transformation>>> {
+        self.keyframes.as_ref()
+    }
+
+    /// Returns the transformation at rest, at the start of any motion.
+    pub fn transformation(&self) -> &Affine {
+        &self.transformation
+    }
+
+    /// Returns the inverse of the transformation at rest, at the start of any motion.
+    pub fn inverse_transformation(&self) -> &Affine {
+        &self.inverse_transformation
+    }
+
+    pub fn transformation_at(&self, time: f64) -> Cow<'_, Affine> {
+        match &self.keyframes {
+            Some(keyframes) => Cow::Owned(Affine::from(&transform::transform(
+                &keyframes.value_at(time),
+            ))),
+            None => Cow::Borrowed(&self.transformation),
+        }
+    }
+
+    /// Returns the inverse of the transformation at the given time, or `None` if the motion
+    /// flattens the shape at that moment (as a flip from `Scale(1, 1, 1)` to `Scale(-1, 1, 1)`
+    /// does halfway through).
+    pub fn inverse_transformation_at(&self, time: f64) -> Option<Cow<'_, Affine>> {
+        match &self.keyframes {
+            Some(_) => self.transformation_at(time).inverse().ok().map(Cow::Owned),
+            None => Some(Cow::Borrowed(&self.inverse_transformation)),
+        }
+    }
+
+    /// Transforms a ray into object space as it is at the ray's time. Returns `None` if the shape
+    /// is flattened at that moment, in which case the ray misses it.
+    pub fn to_object<'a>(&self, world_ray: &'a Ray) -> Option<Cow<'a, Ray>> {
+        if self.identity {
+            return Some(Cow::Borrowed(world_ray));
+        }
+
+        let inverse_transformation = self.inverse_transformation_at(world_ray.time())?;
+
+        Some(Cow::Owned(inverse_transformation.as_ref() * world_ray))
+    }
+
+    /// Transforms a point from world space to object space as it is at the given time. Rays never
+    /// hit flattened shapes, so points on them are never needed, and pass through unchanged.
+    pub fn to_object_point(&self, world_point: &Point, time: f64) -> Point {
+        match self.inverse_transformation_at(time) {
+            Some(inverse_transformation) => inverse_transformation.as_ref() * world_point,
+            None => *world_point,
+        }
+    }
+
+    /// Transforms a surface normal from object space to a unit normal in world space as it is at
+    /// the given time. Like points, normals on flattened shapes pass through unchanged.
+    pub fn to_world_normal(&self, object_normal: &Vector, time: f64) -> Vector {
+        if self.identity {
+            return *object_normal;
+        }
+
+        match self.normal_matrix_at(time) {
+            Some(normal_matrix) => stretched_normal(&normal_matrix, object_normal)
+                .0
+                .normalize(),
+            None => *object_normal,
+        }
+    }
+
+    /// Transforms a point sampled on a shape's surface in object space, along with the surface
+    /// normal there and the probability density (per unit area) of choosing it, into world space
+    /// as it is at the given time. The density thins out wherever the transformation stretches
+    /// the surface, and is infinite on flattened shapes, which have no area to emit light from.
+    pub fn to_world_sample(
+        &self,
+        (point, normal, pdf): (Point, Vector, f64),
+        time: f64,
+    ) -> (Point, Vector, f64) {
+        if self.identity {
+            return (point, normal, pdf);
+        }
+
+        let point = self.transformation_at(time).as_ref() * &point;
+
+        match self.normal_matrix_at(time) {
+            Some(normal_matrix) => {
+                let (normal, stretch) = stretched_normal(&normal_matrix, &normal);
+
+                (point, normal.normalize(), pdf / stretch)
+            }
+            None => (point, normal, f64::INFINITY),
+        }
+    }
+
+    /// Returns the factor by which the transformation at rest scales areas of a surface with the
+    /// given unit normal in object space.
+    pub fn area_scale(&self, object_normal: &Vector) -> f64 {
+        stretched_normal(&self.normal_matrix, object_normal).1
+    }
+
+    /// Returns the matrix that carries normals from object space to world space at the given
+    /// time, or `None` if the shape is flattened at that moment.
+    fn normal_matrix_at(&self, time: f64) -> Option<Cow<'_, Matrix<3>>> {
+        match &self.keyframes {
+            Some(_) => {
+                let inverse_transformation = self.inverse_transformation_at(time)?;
+
+                Some(Cow::Owned(inverse_transformation.linear().transpose()))
+            }
+            None => Some(Cow::Borrowed(&self.normal_matrix)),
+        }
+    }
+}
+
+/// Returns the world space normal, before normalization, of a surface with the given unit normal
+/// in object space, and the factor by which the transformation whose normal matrix is given scales
+/// areas of the surface, |det L| |L^-T n| for its linear part L.
+fn stretched_normal(normal_matrix: &Matrix<3>, object_normal: &Vector) -> (Vector, f64) {
+    let [x, y, z, _] = object_normal.components();
+    let [x, y, z] = normal_matrix * [x, y, z];
+    let normal = Vector::new(x, y, z);
+
+    (
+        normal,
+        normal.magnitude() / normal_matrix.determinant().abs(),
+    )
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::animation::easing::Easing;
+    use crate::animation::track::Track;
+    use crate::ray::Ray;
+    use crate::shape::placement::Placement;
+    use crate::transform::Transformation;
+    use crate::vector::{Point, Vector};
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_to_world_sample() {
+        let placement = Placement::new(&[
+            Transformation::Scale(2.0, 3.0, 4.0),
+            Transformation::Translate(1.0, 0.0, 0.0),
+        ]);
+
+        // A patch of the xy-plane grows six times larger, and a patch of the yz-plane twelve
+        let (point, normal, pdf) = placement.to_world_sample(
+            (Point::new(1.0, 1.0, 0.0), Vector::new(0.0, 0.0, 1.0), 1.0),
+            0.0,
+        );
+
+        Point::new(3.0, 3.0, 0.0).assert_approx_eq(&point);
+        Vector::new(0.0, 0.0, 1.0).assert_approx_eq(&normal);
+        assert_f64_near!(1.0 / 6.0, pdf);
+        assert_f64_near!(12.0, placement.area_scale(&Vector::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_keyframes() {
+        let placement = Placement::with_keyframes(
+            Track::new(0.0, vec![Transformation::Scale(1.0, 1.0, 1.0)]).with_keyframe(
+                1.0,
+                vec![Transformation::Scale(3.0, 3.0, 3.0)],
+                Easing::Linear,
+            ),
+        );
+
+        // The placement rests at its first keyframe, but samples and rays see it as it is at
+        // their time
+        let normal = Vector::new(0.0, 1.0, 0.0);
+
+        assert_f64_near!(1.0, placement.area_scale(&normal));
+        assert_f64_near!(
+            0.25,
+            placement
+                .to_world_sample((Point::new(0.0, 1.0, 0.0), normal, 1.0), 0.5)
+                .2
+        );
+
+        let ray = Ray::new(Point::new(0.0, 0.0, -4.0), Vector::new(0.0, 0.0, 2.0)).with_time(1.0);
+        let object_ray = placement.to_object(&ray).unwrap();
+
+        Point::new(0.0, 0.0, -4.0 / 3.0).assert_approx_eq(object_ray.origin());
+        Vector::new(0.0, 0.0, 2.0 / 3.0).assert_approx_eq(object_ray.direction());
+    }
+
+    #[test]
+    fn test_flattened() {
+        // A flip through the yz-plane flattens the shape halfway through
+        let placement = Placement::with_keyframes(
+            Track::new(0.0, vec![Transformation::Scale(1.0, 1.0, 1.0)]).with_keyframe(
+                1.0,
+                vec![Transformation::Scale(-1.0, 1.0, 1.0)],
+                Easing::Linear,
+            ),
+        );
+
+        let ray = Ray::new(Point::new(-4.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+
+        assert!(placement.to_object(&ray.with_time(0.5)).is_none());
+        assert!(placement.inverse_transformation_at(0.5).is_none());
+
+        let (point, _, pdf) = placement.to_world_sample(
+            (Point::new(1.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0), 1.0),
+            0.5,
+        );
+
+        Point::new(0.0, 0.0, 0.0).assert_approx_eq(&point);
+        assert!(pdf.is_infinite());
+    }
+}
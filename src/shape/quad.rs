@@ -0,0 +1,222 @@
+use crate::animation::track::Track;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::random::Random;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::shape::placement::Placement;
+use crate::shape::triangle::PARALLEL_EPSILON;
+use crate::transform::Transformation;
+use crate::vector::{Point, Vector};
+
+/// A flat parallelogram (a rectangle, if its edges are perpendicular) in world space, or in
+/// object space for moving quads, with one corner at `corner` and edges `u` and `v`. Its normal is
+/// `v × u`.
+pub struct Quad {
+    corner: Point,
+    u: Vector,
+    v: Vector,
+    normal: Vector,
+    placement: Placement,
+    material: Material,
+}
+
+impl Quad {
+    pub fn new(corner: Point, u: Vector, v: Vector, material: Material) -> Self {
+        Quad {
+            corner,
+            u,
+            v,
+            normal: v.cross(&u).normalize(),
+            placement: Placement::default(),
+            material,
+        }
+    }
+
+    /// Makes this quad move as its keyframes dictate, treating its corner and edges as being in
+    /// object space.
+    pub fn with_keyframes(mut self, keyframes: Track<Vec<Transformation>>) -> Self {
+        self.placement = Placement::with_keyframes(keyframes);
+        self
+    }
+
+    pub fn corner(&self) -> &Point {
+        &self.corner
+    }
+
+    pub fn u(&self) -> &Vector {
+        &self.u
+    }
+
+    pub fn v(&self) -> &Vector {
+        &self.v
+    }
+}
+
+impl Shape for Quad {
+    fn placement(&self) -> &Placement {
+        &self.placement
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn normal_at(&self, _world_point: &Point, time: f64) -> Vector {
+        self.placement.to_world_normal(&self.normal, time)
+    }
+
+    fn intersect(&self, world_ray: &Ray) -> Vec<Intersection<'_>> {
+        // The same approach as for triangles, but with both edge coordinates in [0, 1]
+        let Some(ray) = self.placement.to_object(world_ray) else {
+            return Vec::new();
+        };
+        let direction_cross_v = ray.direction().cross(&self.v);
+        let determinant = self.u.dot(&direction_cross_v);
+
+        if determinant.abs() < PARALLEL_EPSILON {
+            return Vec::new();
+        }
+
+        let f = 1.0 / determinant;
+        let corner_to_origin = ray.origin() - &self.corner;
+        let s = f * corner_to_origin.dot(&direction_cross_v);
+
+        if !(0.0..=1.0).contains(&s) {
+            return Vec::new();
+        }
+
+        let origin_cross_u = corner_to_origin.cross(&self.u);
+        let t = f * ray.direction().dot(&origin_cross_u);
+
+        if !(0.0..=1.0).contains(&t) {
+            return Vec::new();
+        }
+
+        vec![Intersection::new(f * self.v.dot(&origin_cross_u), self)]
+    }
+
+    fn area(&self) -> f64 {
+        self.placement.area_scale(&self.normal) * self.u.cross(&self.v).magnitude()
+    }
+
+    fn sample_surface(&self, time: f64, random: &mut Random) -> (Point, Vector, f64) {
+        let (s, t) = (random.next_f64(), random.next_f64());
+
+        self.placement.to_world_sample(
+            (
+                self.corner + &((self.u * s) + &(self.v * t)),
+                self.normal,
+                1.0 / self.u.cross(&self.v).magnitude(),
+            ),
+            time,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::animation::easing::Easing;
+    use crate::animation::track::Track;
+    use crate::material::Material;
+    use crate::random::Random;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::shape::quad::Quad;
+    use crate::transform::Transformation;
+    use crate::vector::{Point, Vector};
+    use assert_float_eq::assert_f64_near;
+
+    fn quad() -> Quad {
+        Quad::new(
+            Point::new(-1.0, 0.0, -2.0),
+            Vector::new(2.0, 0.0, 0.0),
+            Vector::new(0.0, 0.0, 4.0),
+            Material::default(),
+        )
+    }
+
+    #[test]
+    fn test_intersect() {
+        let quad = quad();
+
+        let intersections = quad.intersect(&Ray::new(
+            Point::new(0.5, 3.0, 1.5),
+            Vector::new(0.0, -1.0, 0.0),
+        ));
+
+        assert_eq!(1, intersections.len());
+        assert_f64_near!(3.0, intersections[0].distance());
+
+        // Beyond each edge, and parallel
+        for origin in [
+            Point::new(1.5, 3.0, 0.0),
+            Point::new(-1.5, 3.0, 0.0),
+            Point::new(0.0, 3.0, 2.5),
+            Point::new(0.0, 3.0, -2.5),
+        ] {
+            assert!(
+                quad.intersect(&Ray::new(origin, Vector::new(0.0, -1.0, 0.0)))
+                    .is_empty()
+            );
+        }
+
+        assert!(
+            quad.intersect(&Ray::new(
+                Point::new(0.0, 0.0, -5.0),
+                Vector::new(0.0, 0.0, 1.0)
+            ))
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_area_and_sample_surface() {
+        let quad = quad();
+        let mut random = Random::new(0);
+
+        assert_f64_near!(8.0, quad.area());
+        Vector::new(0.0, 1.0, 0.0)
+            .assert_approx_eq(&quad.normal_at(&Point::new(0.0, 0.0, 0.0), 0.0));
+
+        for _ in 0..100 {
+            let (point, _, pdf) = quad.sample_surface(0.0, &mut random);
+            let (x, y, z) = point.coordinates();
+
+            assert!((-1.0..=1.0).contains(&x));
+            assert_f64_near!(0.0, y);
+            assert!((-2.0..=2.0).contains(&z));
+            assert_f64_near!(1.0 / 8.0, pdf);
+        }
+    }
+
+    #[test]
+    fn test_keyframes() {
+        let quad = quad().with_keyframes(
+            Track::new(0.0, vec![Transformation::Scale(1.0, 1.0, 1.0)]).with_keyframe(
+                1.0,
+                vec![Transformation::Scale(2.0, 1.0, 2.0)],
+                Easing::Linear,
+            ),
+        );
+        let mut random = Random::new(0);
+
+        // The quad's area is measured at rest, but samples are spread over it as it is at their
+        // time
+        assert_f64_near!(8.0, quad.area());
+
+        for _ in 0..100 {
+            let (point, _, pdf) = quad.sample_surface(1.0, &mut random);
+            let (x, _, z) = point.coordinates();
+
+            assert!((-2.0..=2.0).contains(&x));
+            assert!((-4.0..=4.0).contains(&z));
+            assert_f64_near!(1.0 / 32.0, pdf);
+        }
+
+        let ray = Ray::new(Point::new(1.5, 3.0, 3.0), Vector::new(0.0, -1.0, 0.0));
+
+        assert!(quad.intersect(&ray).is_empty());
+        assert_f64_near!(3.0, quad.intersect(&ray.with_time(1.0))[0].distance());
+    }
+}
@@ -1,50 +1,62 @@
+use crate::animation::track::Track;
 use crate::intersection::Intersection;
 use crate::material::Material;
-use crate::matrix::Matrix;
+use crate::random::Random;
 use crate::ray::Ray;
+use crate::sampling::warp::uniform_sample_sphere;
 use crate::shape::Shape;
-use crate::transform;
+use crate::shape::placement::Placement;
 use crate::transform::Transformation;
 use crate::vector::{ORIGIN, Point, Vector};
+use std::f64::consts::PI;
 
+/// The exponent used in Knud Thomsen's approximation of the surface area of an ellipsoid.
+const THOMSEN_EXPONENT: f64 = 1.6075;
+
+#[derive(Default)]
 pub struct Sphere {
-    inverse_transformation: Matrix<4>,
+    placement: Placement,
     material: Material,
 }
 
 impl Sphere {
     pub fn with_transformations(transformations: &[Transformation], material: Material) -> Self {
         Sphere {
-            inverse_transformation: transform::transform(transformations).inverse().unwrap(),
+            placement: Placement::new(transformations),
+            material,
+        }
+    }
+
+    /// Creates a sphere that moves as its keyframes dictate, which blurs it in renders whose
+    /// camera shutter stays open while it moves.
+    pub fn with_keyframes(keyframes: Track<Vec<Transformation>>, material: Material) -> Self {
+        Sphere {
+            placement: Placement::with_keyframes(keyframes),
             material,
         }
     }
 }
 
 impl Shape for Sphere {
-    fn inverse_transformation(&self) -> &Matrix<4> {
-        &self.inverse_transformation
+    fn placement(&self) -> &Placement {
+        &self.placement
     }
 
     fn material(&self) -> &Material {
         &self.material
     }
 
-    fn normal_at(&self, world_point: &Point) -> Vector {
-        let object_point = &self.inverse_transformation * world_point;
-        let object_normal = object_point - &ORIGIN;
-
-        let mut world_normal_components =
-            &self.inverse_transformation.transpose() * object_normal.components();
+    fn normal_at(&self, world_point: &Point, time: f64) -> Vector {
+        let object_point = self.placement.to_object_point(world_point, time);
 
-        // TODO Possible optimization opportunity: invert a 3x3 matrix instead?
-        world_normal_components[3] = 0.0;
-
-        Vector::from(world_normal_components).normalize()
+        self.placement
+            .to_world_normal(&(object_point - &ORIGIN), time)
     }
 
-    fn intersect(&self, world_ray: &Ray) -> Vec<Intersection> {
-        let ray = &self.inverse_transformation * world_ray;
+    fn intersect(&self, world_ray: &Ray) -> Vec<Intersection<'_>> {
+        let Some(ray) = self.placement.to_object(world_ray) else {
+            return Vec::new();
+        };
         let sphere_to_ray = ray.origin() - &ORIGIN;
 
         let a = ray.direction().dot(ray.direction());
@@ -70,26 +82,48 @@ impl Shape for Sphere {
             ]
         }
     }
-}
 
-impl Default for Sphere {
-    fn default() -> Self {
-        Sphere {
-            inverse_transformation: Matrix::<4>::identity(),
-            material: Material::default(),
-        }
+    /// Returns the surface area of this sphere at the start of its motion, which is exact for
+    /// uniformly-scaled spheres and within about 1% for ellipsoids.
+    fn area(&self) -> f64 {
+        let [a, b, c] = [
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ]
+        .map(|axis| {
+            (self.placement.transformation() * &axis)
+                .magnitude()
+                .powf(THOMSEN_EXPONENT)
+        });
+
+        4.0 * PI * (((a * b) + (a * c) + (b * c)) / 3.0).powf(1.0 / THOMSEN_EXPONENT)
+    }
+
+    /// Samples a point on this sphere's surface by transforming a uniformly-distributed point on
+    /// the unit sphere. Points on ellipsoids are denser where the transformation stretches the
+    /// surface less, which the returned density accounts for.
+    fn sample_surface(&self, time: f64, random: &mut Random) -> (Point, Vector, f64) {
+        let direction = uniform_sample_sphere(random);
+
+        self.placement
+            .to_world_sample((ORIGIN + &direction, direction, 1.0 / (4.0 * PI)), time)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::animation::easing::Easing;
+    use crate::animation::track::Track;
     use crate::material::Material;
+    use crate::random::Random;
     use crate::ray::Ray;
     use crate::shape::Shape;
     use crate::shape::sphere::Sphere;
     use crate::transform::Transformation;
     use crate::vector::{Point, Vector};
     use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+    use std::f64::consts::PI;
 
     #[test]
     fn test_intersect_ray() {
@@ -179,18 +213,19 @@ mod test {
         let unit_sphere = Sphere::default();
 
         Vector::new(1.0, 0.0, 0.0)
-            .assert_approx_eq(&unit_sphere.normal_at(&Point::new(1.0, 0.0, 0.0)));
+            .assert_approx_eq(&unit_sphere.normal_at(&Point::new(1.0, 0.0, 0.0), 0.0));
 
         Vector::new(0.0, 1.0, 0.0)
-            .assert_approx_eq(&unit_sphere.normal_at(&Point::new(0.0, 1.0, 0.0)));
+            .assert_approx_eq(&unit_sphere.normal_at(&Point::new(0.0, 1.0, 0.0), 0.0));
 
         Vector::new(0.0, 0.0, 1.0)
-            .assert_approx_eq(&unit_sphere.normal_at(&Point::new(0.0, 0.0, 1.0)));
+            .assert_approx_eq(&unit_sphere.normal_at(&Point::new(0.0, 0.0, 1.0), 0.0));
 
         let sqrt_3_3 = 3.0f64.sqrt() / 3.0;
 
-        Vector::new(sqrt_3_3, sqrt_3_3, sqrt_3_3)
-            .assert_approx_eq(&unit_sphere.normal_at(&Point::new(sqrt_3_3, sqrt_3_3, sqrt_3_3)));
+        Vector::new(sqrt_3_3, sqrt_3_3, sqrt_3_3).assert_approx_eq(
+            &unit_sphere.normal_at(&Point::new(sqrt_3_3, sqrt_3_3, sqrt_3_3), 0.0),
+        );
     }
 
     #[test]
@@ -204,7 +239,7 @@ mod test {
             );
 
             Vector::new(0.0, sqrt_2_2, -sqrt_2_2).assert_approx_eq(
-                &translated_sphere.normal_at(&Point::new(0.0, 1.0 + sqrt_2_2, -sqrt_2_2)),
+                &translated_sphere.normal_at(&Point::new(0.0, 1.0 + sqrt_2_2, -sqrt_2_2), 0.0),
             );
         }
 
@@ -217,11 +252,127 @@ mod test {
                 Material::default(),
             );
 
-            let normal = translated_sphere.normal_at(&Point::new(0.0, sqrt_2_2, -sqrt_2_2));
+            let normal = translated_sphere.normal_at(&Point::new(0.0, sqrt_2_2, -sqrt_2_2), 0.0);
 
             assert_f64_near!(0.0, normal.components()[0]);
             assert_float_absolute_eq!(0.97014, normal.components()[1], 1e-5);
             assert_float_absolute_eq!(-0.24254, normal.components()[2], 1e-5);
         }
     }
+
+    #[test]
+    fn test_area() {
+        assert_f64_near!(4.0 * PI, Sphere::default().area());
+
+        let sphere = Sphere::with_transformations(
+            &[
+                Transformation::Scale(2.0, 2.0, 2.0),
+                Transformation::RotateY(0.3),
+                Transformation::Translate(1.0, 2.0, 3.0),
+            ],
+            Material::default(),
+        );
+
+        assert_float_absolute_eq!(16.0 * PI, sphere.area(), 1e-9);
+
+        // An oblate spheroid with semi-axes 2, 2 and 1 has an area of about 34.69
+        let spheroid = Sphere::with_transformations(
+            &[Transformation::Scale(2.0, 1.0, 2.0)],
+            Material::default(),
+        );
+
+        assert_float_absolute_eq!(34.69, spheroid.area(), 0.35);
+    }
+
+    #[test]
+    fn test_sample_surface() {
+        let sphere = Sphere::with_transformations(
+            &[
+                Transformation::Scale(2.0, 2.0, 2.0),
+                Transformation::Translate(1.0, 2.0, 3.0),
+            ],
+            Material::default(),
+        );
+
+        let center = Point::new(1.0, 2.0, 3.0);
+        let mut random = Random::new(0);
+
+        for _ in 0..100 {
+            let (point, normal, pdf) = sphere.sample_surface(0.0, &mut random);
+
+            assert_float_absolute_eq!(2.0, (&point - &center).magnitude(), 1e-9);
+            ((&point - &center) / 2.0).assert_approx_eq_epsilon(&normal, 1e-9);
+            assert_float_absolute_eq!(1.0 / (16.0 * PI), pdf, 1e-12);
+        }
+
+        // Points on an ellipsoid aren't uniformly distributed, but weighting each by its density
+        // still recovers the oblate spheroid's area of about 34.69
+        let spheroid = Sphere::with_transformations(
+            &[Transformation::Scale(2.0, 1.0, 2.0)],
+            Material::default(),
+        );
+
+        let mut area = 0.0;
+
+        for _ in 0..10_000 {
+            let (point, normal, pdf) = spheroid.sample_surface(0.0, &mut random);
+
+            normal.assert_approx_eq_epsilon(&spheroid.normal_at(&point, 0.0), 1e-9);
+            area += 1.0 / pdf;
+        }
+
+        assert_float_absolute_eq!(34.69, area / 10_000.0, 0.1);
+    }
+
+    #[test]
+    fn test_keyframes() {
+        let sphere = Sphere::with_keyframes(
+            Track::new(0.0, vec![Transformation::Translate(0.0, 0.0, 0.0)]).with_keyframe(
+                1.0,
+                vec![Transformation::Translate(4.0, 0.0, 0.0)],
+                Easing::Linear,
+            ),
+            Material::default(),
+        );
+
+        let ray =
+            |time| Ray::new(Point::new(2.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0)).with_time(time);
+
+        // The sphere passes through the ray's path halfway through its motion
+        assert!(sphere.intersect(&ray(0.0)).is_empty());
+        assert!(sphere.intersect(&ray(1.0)).is_empty());
+
+        let intersections = sphere.intersect(&ray(0.5));
+
+        assert_eq!(2, intersections.len());
+        assert_f64_near!(4.0, intersections[0].distance());
+
+        Vector::new(1.0, 0.0, 0.0)
+            .assert_approx_eq(&sphere.normal_at(&Point::new(3.0, 0.0, 0.0), 0.5));
+        Vector::new(-1.0, 0.0, 0.0)
+            .assert_approx_eq(&sphere.normal_at(&Point::new(3.0, 0.0, 0.0), 1.0));
+
+        let mut random = Random::new(0);
+        let (point, _, _) = sphere.sample_surface(0.75, &mut random);
+
+        assert_float_absolute_eq!(1.0, (&point - &Point::new(3.0, 0.0, 0.0)).magnitude(), 1e-9);
+
+        // Emitters are chosen by their area at rest, but the density of samples on a growing
+        // sphere reflects its size at the sampled time
+        let growing = Sphere::with_keyframes(
+            Track::new(0.0, vec![Transformation::Scale(1.0, 1.0, 1.0)]).with_keyframe(
+                1.0,
+                vec![Transformation::Scale(2.0, 2.0, 2.0)],
+                Easing::Linear,
+            ),
+            Material::default(),
+        );
+
+        assert_f64_near!(4.0 * PI, growing.area());
+        assert_float_absolute_eq!(
+            1.0 / (16.0 * PI),
+            growing.sample_surface(1.0, &mut random).2,
+            1e-12
+        );
+    }
 }
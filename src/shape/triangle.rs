@@ -0,0 +1,236 @@
+use crate::animation::track::Track;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::random::Random;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::shape::placement::Placement;
+use crate::transform::Transformation;
+use crate::vector::{Point, Vector};
+
+/// Determinants smaller than this indicate that a ray is parallel to a triangle or quad.
+pub(crate) const PARALLEL_EPSILON: f64 = 1e-12;
+
+/// A flat triangle whose vertices are given in world space, or in object space for moving
+/// triangles. Its normal points toward the side from which the vertices appear in clockwise order.
+pub struct Triangle {
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    e1: Vector,
+    e2: Vector,
+    normal: Vector,
+    placement: Placement,
+    material: Material,
+}
+
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point, material: Material) -> Self {
+        let e1 = &p2 - &p1;
+        let e2 = &p3 - &p1;
+
+        Triangle {
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: e2.cross(&e1).normalize(),
+            placement: Placement::default(),
+            material,
+        }
+    }
+
+    /// Makes this triangle move as its keyframes dictate, treating its vertices as being in
+    /// object space.
+    pub fn with_keyframes(mut self, keyframes: Track<Vec<Transformation>>) -> Self {
+        self.placement = Placement::with_keyframes(keyframes);
+        self
+    }
+
+    pub fn vertices(&self) -> [&Point; 3] {
+        [&self.p1, &self.p2, &self.p3]
+    }
+}
+
+impl Shape for Triangle {
+    fn placement(&self) -> &Placement {
+        &self.placement
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn normal_at(&self, _world_point: &Point, time: f64) -> Vector {
+        self.placement.to_world_normal(&self.normal, time)
+    }
+
+    fn intersect(&self, world_ray: &Ray) -> Vec<Intersection<'_>> {
+        // Möller-Trumbore: solve for the ray distance and the barycentric coordinates (u, v) of
+        // the intersection all at once
+        let Some(ray) = self.placement.to_object(world_ray) else {
+            return Vec::new();
+        };
+        let direction_cross_e2 = ray.direction().cross(&self.e2);
+        let determinant = self.e1.dot(&direction_cross_e2);
+
+        if determinant.abs() < PARALLEL_EPSILON {
+            return Vec::new();
+        }
+
+        let f = 1.0 / determinant;
+        let p1_to_origin = ray.origin() - &self.p1;
+        let u = f * p1_to_origin.dot(&direction_cross_e2);
+
+        if !(0.0..=1.0).contains(&u) {
+            return Vec::new();
+        }
+
+        let origin_cross_e1 = p1_to_origin.cross(&self.e1);
+        let v = f * ray.direction().dot(&origin_cross_e1);
+
+        if v < 0.0 || (u + v) > 1.0 {
+            return Vec::new();
+        }
+
+        vec![Intersection::new(f * self.e2.dot(&origin_cross_e1), self)]
+    }
+
+    fn area(&self) -> f64 {
+        self.placement.area_scale(&self.normal) * self.e1.cross(&self.e2).magnitude() / 2.0
+    }
+
+    fn sample_surface(&self, time: f64, random: &mut Random) -> (Point, Vector, f64) {
+        let (mut u, mut v) = (random.next_f64(), random.next_f64());
+
+        // Fold points from the far half of the parallelogram spanned by the edges back into the
+        // triangle
+        if u + v > 1.0 {
+            (u, v) = (1.0 - u, 1.0 - v);
+        }
+
+        let object_area = self.e1.cross(&self.e2).magnitude() / 2.0;
+
+        self.placement.to_world_sample(
+            (
+                self.p1 + &((self.e1 * u) + &(self.e2 * v)),
+                self.normal,
+                1.0 / object_area,
+            ),
+            time,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::animation::easing::Easing;
+    use crate::animation::track::Track;
+    use crate::material::Material;
+    use crate::random::Random;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::shape::triangle::Triangle;
+    use crate::transform::Transformation;
+    use crate::vector::{Point, Vector};
+    use assert_float_eq::assert_f64_near;
+    use std::f64::consts::PI;
+
+    fn triangle() -> Triangle {
+        Triangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Material::default(),
+        )
+    }
+
+    #[test]
+    fn test_normal_at() {
+        let triangle = triangle();
+
+        Vector::new(0.0, 0.0, -1.0)
+            .assert_approx_eq(&triangle.normal_at(&Point::new(0.0, 0.5, 0.0), 0.0));
+        Vector::new(0.0, 0.0, -1.0)
+            .assert_approx_eq(&triangle.normal_at(&Point::new(-0.5, 0.75, 0.0), 0.0));
+    }
+
+    #[test]
+    fn test_intersect() {
+        let triangle = triangle();
+
+        // Parallel to the triangle
+        assert!(
+            triangle
+                .intersect(&Ray::new(
+                    Point::new(0.0, -1.0, -2.0),
+                    Vector::new(0.0, 1.0, 0.0)
+                ))
+                .is_empty()
+        );
+
+        // Past each edge
+        for origin in [
+            Point::new(1.0, 1.0, -2.0),
+            Point::new(-1.0, 1.0, -2.0),
+            Point::new(0.0, -1.0, -2.0),
+        ] {
+            assert!(
+                triangle
+                    .intersect(&Ray::new(origin, Vector::new(0.0, 0.0, 1.0)))
+                    .is_empty()
+            );
+        }
+
+        let intersections = triangle.intersect(&Ray::new(
+            Point::new(0.0, 0.5, -2.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ));
+
+        assert_eq!(1, intersections.len());
+        assert_f64_near!(2.0, intersections[0].distance());
+    }
+
+    #[test]
+    fn test_area_and_sample_surface() {
+        let triangle = triangle();
+        let mut random = Random::new(0);
+
+        assert_f64_near!(1.0, triangle.area());
+
+        for _ in 0..100 {
+            let (point, normal, pdf) = triangle.sample_surface(0.0, &mut random);
+            let (x, y, z) = point.coordinates();
+
+            assert_f64_near!(0.0, z);
+            assert!(y >= 0.0 && y <= 1.0 - x.abs());
+            Vector::new(0.0, 0.0, -1.0).assert_approx_eq(&normal);
+            assert_f64_near!(1.0, pdf);
+        }
+    }
+
+    #[test]
+    fn test_keyframes() {
+        let triangle = triangle().with_keyframes(
+            Track::new(0.0, vec![Transformation::RotateY(0.0)]).with_keyframe(
+                1.0,
+                vec![Transformation::RotateY(PI)],
+                Easing::Linear,
+            ),
+        );
+
+        // Halfway through its motion, the triangle lies edge-on to the ray
+        let ray =
+            |time| Ray::new(Point::new(0.0, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0)).with_time(time);
+
+        assert_f64_near!(2.0, triangle.intersect(&ray(0.0))[0].distance());
+        assert!(triangle.intersect(&ray(0.5)).is_empty());
+        assert_f64_near!(2.0, triangle.intersect(&ray(1.0))[0].distance());
+
+        Vector::new(0.0, 0.0, -1.0)
+            .assert_approx_eq(&triangle.normal_at(&Point::new(0.0, 0.5, 0.0), 0.0));
+        Vector::new(0.0, 0.0, 1.0)
+            .assert_approx_eq_epsilon(&triangle.normal_at(&Point::new(0.0, 0.5, 0.0), 1.0), 1e-12);
+    }
+}
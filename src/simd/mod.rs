@@ -0,0 +1,67 @@
+#[cfg(any(not(all(feature = "simd", target_arch = "x86_64")), test))]
+pub mod scalar;
+
+/// SSE2 versions of the scalar operations, which every x86_64 processor supports, with AVX
+/// versions of the matrix products when the crate is compiled with AVX enabled (with
+/// `-C target-cpu=native`, for example). Sums are accumulated in the same order as the scalar
+/// code, so results should match it exactly.
+#[cfg(all(target_arch = "x86_64", any(feature = "simd", test)))]
+pub mod x86_64;
+
+// Vector and matrix arithmetic on raw components, vectorized with the `simd` feature where the
+// target supports it
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+pub use scalar::{cross, dot, mul_matrix, mul_matrix_vector, normalize};
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+pub use x86_64::{cross, dot, mul_matrix, mul_matrix_vector, normalize};
+
+#[cfg(all(test, target_arch = "x86_64"))]
+mod test {
+    use crate::random::Random;
+    use crate::simd::{scalar, x86_64};
+    use assert_float_eq::assert_f64_near;
+
+    fn random_components(random: &mut Random) -> [f64; 4] {
+        [0.0; 4].map(|_| (random.next_f64() - 0.5) * 200.0)
+    }
+
+    fn assert_components_near(expected: &[f64], actual: &[f64]) {
+        for (expected, actual) in expected.iter().zip(actual) {
+            assert_f64_near!(*expected, *actual);
+        }
+    }
+
+    #[test]
+    fn test_vector_operations() {
+        let mut random = Random::new(0);
+
+        for _ in 0..10_000 {
+            let a = random_components(&mut random);
+            let b = random_components(&mut random);
+
+            assert_f64_near!(scalar::dot(&a, &b), x86_64::dot(&a, &b));
+            assert_components_near(&scalar::cross(&a, &b), &x86_64::cross(&a, &b));
+            assert_components_near(&scalar::normalize(&a), &x86_64::normalize(&a));
+        }
+    }
+
+    #[test]
+    fn test_matrix_operations() {
+        let mut random = Random::new(0);
+
+        for _ in 0..10_000 {
+            let a = [0; 4].map(|_| random_components(&mut random));
+            let b = [0; 4].map(|_| random_components(&mut random));
+            let v = random_components(&mut random);
+
+            assert_components_near(
+                &scalar::mul_matrix_vector(&a, &v),
+                &x86_64::mul_matrix_vector(&a, &v),
+            );
+            assert_components_near(
+                scalar::mul_matrix(&a, &b).as_flattened(),
+                x86_64::mul_matrix(&a, &b).as_flattened(),
+            );
+        }
+    }
+}
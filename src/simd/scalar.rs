@@ -0,0 +1,34 @@
+/// Returns the dot product of the x, y and z components of two vectors.
+pub fn dot(a: &[f64; 4], b: &[f64; 4]) -> f64 {
+    (a[0] * b[0]) + (a[1] * b[1]) + (a[2] * b[2])
+}
+
+pub fn cross(a: &[f64; 4], b: &[f64; 4]) -> [f64; 4] {
+    [
+        (a[1] * b[2]) - (a[2] * b[1]),
+        (a[2] * b[0]) - (a[0] * b[2]),
+        (a[0] * b[1]) - (a[1] * b[0]),
+        0.0,
+    ]
+}
+
+pub fn normalize(a: &[f64; 4]) -> [f64; 4] {
+    let magnitude = dot(a, a).sqrt();
+
+    [a[0] / magnitude, a[1] / magnitude, a[2] / magnitude, 0.0]
+}
+
+pub fn mul_matrix_vector(m: &[[f64; 4]; 4], v: &[f64; 4]) -> [f64; 4] {
+    m.map(|row| (row[0] * v[0]) + (row[1] * v[1]) + (row[2] * v[2]) + (row[3] * v[3]))
+}
+
+pub fn mul_matrix(a: &[[f64; 4]; 4], b: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    // Each row of the product is a row of `a` transformed by the transpose of `b`
+    let columns = transpose(b);
+
+    a.map(|row| mul_matrix_vector(&columns, &row))
+}
+
+fn transpose(m: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    std::array::from_fn(|n| m.map(|row| row[n]))
+}
@@ -0,0 +1,128 @@
+use std::arch::x86_64::*;
+
+pub fn dot(a: &[f64; 4], b: &[f64; 4]) -> f64 {
+    // SAFETY: SSE2 is part of the x86_64 baseline
+    unsafe {
+        let [x, y] = unpack(_mm_mul_pd(pair(a[0], a[1]), pair(b[0], b[1])));
+
+        (x + y) + (a[2] * b[2])
+    }
+}
+
+pub fn cross(a: &[f64; 4], b: &[f64; 4]) -> [f64; 4] {
+    // SAFETY: SSE2 is part of the x86_64 baseline
+    unsafe {
+        // The (y, z) and (z, x) lanes give the first two components at once
+        let [x, y] = unpack(_mm_sub_pd(
+            _mm_mul_pd(pair(a[1], a[2]), pair(b[2], b[0])),
+            _mm_mul_pd(pair(a[2], a[0]), pair(b[1], b[2])),
+        ));
+
+        [x, y, (a[0] * b[1]) - (a[1] * b[0]), 0.0]
+    }
+}
+
+pub fn normalize(a: &[f64; 4]) -> [f64; 4] {
+    // SAFETY: SSE2 is part of the x86_64 baseline
+    unsafe {
+        let magnitude = dot(a, a).sqrt();
+        let [x, y] = unpack(_mm_div_pd(pair(a[0], a[1]), _mm_set1_pd(magnitude)));
+
+        [x, y, a[2] / magnitude, 0.0]
+    }
+}
+
+#[cfg(not(target_feature = "avx"))]
+pub fn mul_matrix_vector(m: &[[f64; 4]; 4], v: &[f64; 4]) -> [f64; 4] {
+    // SAFETY: SSE2 is part of the x86_64 baseline
+    unsafe {
+        // Work down two rows at a time, scaling each column by the matching component
+        let [r0, r1] = mul_columns([0, 1, 2, 3].map(|col| pair(m[0][col], m[1][col])), v);
+        let [r2, r3] = mul_columns([0, 1, 2, 3].map(|col| pair(m[2][col], m[3][col])), v);
+
+        [r0, r1, r2, r3]
+    }
+}
+
+#[cfg(not(target_feature = "avx"))]
+pub fn mul_matrix(a: &[[f64; 4]; 4], b: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    // SAFETY: SSE2 is part of the x86_64 baseline
+    unsafe {
+        // Each row of the product is a sum of the rows of `b`, weighted by a row of `a`
+        a.map(|row| {
+            let [c0, c1] = mul_columns(b.map(|b_row| pair(b_row[0], b_row[1])), &row);
+            let [c2, c3] = mul_columns(b.map(|b_row| pair(b_row[2], b_row[3])), &row);
+
+            [c0, c1, c2, c3]
+        })
+    }
+}
+
+#[cfg(target_feature = "avx")]
+pub fn mul_matrix_vector(m: &[[f64; 4]; 4], v: &[f64; 4]) -> [f64; 4] {
+    // SAFETY: AVX is enabled at compile time
+    unsafe {
+        let columns =
+            [0, 1, 2, 3].map(|col| _mm256_set_pd(m[3][col], m[2][col], m[1][col], m[0][col]));
+
+        unpack_256(mul_columns_256(columns, v))
+    }
+}
+
+#[cfg(target_feature = "avx")]
+pub fn mul_matrix(a: &[[f64; 4]; 4], b: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+    // SAFETY: AVX is enabled at compile time
+    unsafe {
+        let rows = b.map(|row| _mm256_set_pd(row[3], row[2], row[1], row[0]));
+
+        a.map(|row| unpack_256(mul_columns_256(rows, &row)))
+    }
+}
+
+/// Returns the sum of `columns` weighted by the matching `weights`.
+#[cfg(not(target_feature = "avx"))]
+#[target_feature(enable = "sse2")]
+fn mul_columns(columns: [__m128d; 4], weights: &[f64; 4]) -> [f64; 2] {
+    let mut sum = _mm_mul_pd(columns[0], _mm_set1_pd(weights[0]));
+
+    for (column, weight) in columns.iter().zip(weights).skip(1) {
+        sum = _mm_add_pd(sum, _mm_mul_pd(*column, _mm_set1_pd(*weight)));
+    }
+
+    unpack(sum)
+}
+
+/// Returns the sum of `columns` weighted by the matching `weights`.
+#[cfg(target_feature = "avx")]
+#[target_feature(enable = "avx")]
+fn mul_columns_256(columns: [__m256d; 4], weights: &[f64; 4]) -> __m256d {
+    let mut sum = _mm256_mul_pd(columns[0], _mm256_set1_pd(weights[0]));
+
+    for (column, weight) in columns.iter().zip(weights).skip(1) {
+        sum = _mm256_add_pd(sum, _mm256_mul_pd(*column, _mm256_set1_pd(*weight)));
+    }
+
+    sum
+}
+
+#[target_feature(enable = "sse2")]
+fn pair(low: f64, high: f64) -> __m128d {
+    _mm_set_pd(high, low)
+}
+
+#[target_feature(enable = "sse2")]
+fn unpack(pair: __m128d) -> [f64; 2] {
+    [
+        _mm_cvtsd_f64(pair),
+        _mm_cvtsd_f64(_mm_unpackhi_pd(pair, pair)),
+    ]
+}
+
+#[cfg(target_feature = "avx")]
+#[target_feature(enable = "avx")]
+fn unpack_256(lanes: __m256d) -> [f64; 4] {
+    let [a, b] = unpack(_mm256_castpd256_pd128(lanes));
+    let [c, d] = unpack(_mm256_extractf128_pd::<1>(lanes));
+
+    [a, b, c, d]
+}
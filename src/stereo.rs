@@ -0,0 +1,239 @@
+use crate::camera::Camera;
+use crate::canvas::Canvas;
+use crate::color::Color;
+use crate::matrix::Matrix;
+use crate::transform;
+use crate::transform::Transformation;
+
+/// How a stereo rig aims its two eyes at the point where they converge.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Convergence {
+    /// Both eyes rotate inward to look at the convergence point. Simple, but the eyes' canvases
+    /// no longer lie in the same plane, which introduces vertical parallax toward the corners.
+    ToeIn,
+    /// Both eyes look straight ahead and shift their canvases toward each other until their
+    /// centers line up at the convergence point (an asymmetric frustum).
+    OffAxis,
+}
+
+/// How the two views of a stereo pair are combined into a single canvas.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StereoLayout {
+    /// The left eye's view on the left half of a canvas twice as wide as each view.
+    SideBySide,
+    /// The left eye's view on the top half of a canvas twice as tall as each view.
+    OverUnder,
+    /// A red-cyan anaglyph, with the left eye's view in the red channel and the right eye's in
+    /// the green and blue channels.
+    Anaglyph,
+}
+
+impl StereoLayout {
+    /// Combines the views of the left and right eyes, which must be the same size.
+    pub fn compose(&self, left: &Canvas, right: &Canvas) -> Canvas {
+        let (width, height) = (left.width(), left.height());
+
+        debug_assert!(right.width() == width && right.height() == height);
+
+        let mut canvas = match self {
+            StereoLayout::SideBySide => Canvas::new(width * 2, height),
+            StereoLayout::OverUnder => Canvas::new(width, height * 2),
+            StereoLayout::Anaglyph => Canvas::new(width, height),
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                match self {
+                    StereoLayout::SideBySide => {
+                        copy_pixel(left, x, y, &mut canvas, x, y);
+                        copy_pixel(right, x, y, &mut canvas, x + width, y);
+                    }
+                    StereoLayout::OverUnder => {
+                        copy_pixel(left, x, y, &mut canvas, x, y);
+                        copy_pixel(right, x, y, &mut canvas, x, y + height);
+                    }
+                    StereoLayout::Anaglyph => {
+                        let [red, _, _] = channels(left.get_pixel(x, y));
+                        let [_, green, blue] = channels(right.get_pixel(x, y));
+
+                        canvas.set_pixel(x, y, Color::new(red, green, blue));
+                        canvas.set_coverage(
+                            x,
+                            y,
+                            left.get_coverage(x, y).max(right.get_coverage(x, y)),
+                        );
+                    }
+                }
+            }
+        }
+
+        canvas
+    }
+}
+
+fn copy_pixel(source: &Canvas, x: usize, y: usize, destination: &mut Canvas, dx: usize, dy: usize) {
+    destination.set_pixel(dx, dy, *source.get_pixel(x, y));
+    destination.set_coverage(dx, dy, source.get_coverage(x, y));
+}
+
+fn channels(color: &Color) -> [f64; 3] {
+    let components = color.components();
+
+    [components[0], components[1], components[2]]
+}
+
+/// A pair of eyes `interocular_distance` apart, centered on a camera and converging on a point
+/// `convergence_distance` in front of it. Objects at the convergence distance appear at the
+/// depth of the screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StereoRig {
+    interocular_distance: f64,
+    convergence_distance: f64,
+    convergence: Convergence,
+}
+
+impl StereoRig {
+    /// Creates an off-axis stereo rig.
+    pub fn new(interocular_distance: f64, convergence_distance: f64) -> Self {
+        Self {
+            interocular_distance,
+            convergence_distance,
+            convergence: Convergence::OffAxis,
+        }
+    }
+
+    pub fn with_convergence(mut self, convergence: Convergence) -> Self {
+        self.convergence = convergence;
+        self
+    }
+
+    pub fn interocular_distance(&self) -> f64 {
+        self.interocular_distance
+    }
+
+    pub fn convergence_distance(&self) -> f64 {
+        self.convergence_distance
+    }
+
+    pub fn convergence(&self) -> &Convergence {
+        &self.convergence
+    }
+
+    /// Returns cameras for the left and right eyes, based on the given camera.
+    pub fn eyes(&self, camera: &Camera) -> (Camera, Camera) {
+        let eye = |offset: f64| {
+            camera
+                .clone()
+                .with_transformation(camera.transformation() * &self.eye_transformation(offset))
+        };
+
+        let half_distance = self.interocular_distance / 2.0;
+
+        (eye(-half_distance), eye(half_distance))
+    }
+
+    /// Returns the transformation from an eye's space to its camera's space for an eye `offset`
+    /// units to the camera's right.
+    fn eye_transformation(&self, offset: f64) -> Matrix<4> {
+        match self.convergence {
+            Convergence::ToeIn => transform::transform(&[
+                Transformation::RotateY(offset.atan2(self.convergence_distance)),
+                Transformation::Translate(offset, 0.0, 0.0),
+            ]),
+            // Shearing x by z moves the view direction toward the convergence point while
+            // keeping the canvas parallel to the camera's
+            Convergence::OffAxis => transform::transform(&[
+                Transformation::Shear(0.0, offset / self.convergence_distance, 0.0, 0.0, 0.0, 0.0),
+                Transformation::Translate(offset, 0.0, 0.0),
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::camera::Camera;
+    use crate::canvas::Canvas;
+    use crate::color::Color;
+    use crate::random::Random;
+    use crate::stereo::{Convergence, StereoLayout, StereoRig};
+    use crate::vector::Point;
+    use assert_float_eq::assert_float_absolute_eq;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_eyes() {
+        let camera = Camera::new(101, 101, PI / 2.0);
+        let mut random = Random::new(0);
+
+        for convergence in [Convergence::ToeIn, Convergence::OffAxis] {
+            let (left, right) = StereoRig::new(0.5, 10.0)
+                .with_convergence(convergence)
+                .eyes(&camera);
+
+            let left_ray = left.ray_for_pixel(50.5, 50.5, &mut random).unwrap();
+            let right_ray = right.ray_for_pixel(50.5, 50.5, &mut random).unwrap();
+
+            Point::new(-0.25, 0.0, 0.0).assert_approx_eq(left_ray.origin());
+            Point::new(0.25, 0.0, 0.0).assert_approx_eq(right_ray.origin());
+
+            // The centers of both eyes' views meet at the convergence point
+            for ray in [left_ray, right_ray] {
+                let distance = -10.0 / ray.direction().components()[2];
+
+                Point::new(0.0, 0.0, -10.0).assert_approx_eq_epsilon(&ray.position(distance), 1e-9);
+            }
+        }
+
+        // Off-axis eyes keep looking straight ahead, so rays through the same pixel away from the
+        // center stay level with each other
+        let (left, right) = StereoRig::new(0.5, 10.0).eyes(&camera);
+        let left_ray = left.ray_for_pixel(0.0, 0.0, &mut random).unwrap();
+        let right_ray = right.ray_for_pixel(0.0, 0.0, &mut random).unwrap();
+
+        let [_, left_y, left_z, _] = left_ray.direction().components();
+        let [_, right_y, right_z, _] = right_ray.direction().components();
+
+        assert_float_absolute_eq!(left_y / left_z, right_y / right_z, 1e-12);
+
+        // Toe-in eyes don't
+        let (left, right) = StereoRig::new(0.5, 10.0)
+            .with_convergence(Convergence::ToeIn)
+            .eyes(&camera);
+        let left_ray = left.ray_for_pixel(0.0, 0.0, &mut random).unwrap();
+        let right_ray = right.ray_for_pixel(0.0, 0.0, &mut random).unwrap();
+
+        let [_, left_y, left_z, _] = left_ray.direction().components();
+        let [_, right_y, right_z, _] = right_ray.direction().components();
+
+        assert!((left_y / left_z - right_y / right_z).abs() > 1e-6);
+    }
+
+    #[test]
+    fn test_compose() {
+        let mut left = Canvas::new(2, 1);
+        let mut right = Canvas::new(2, 1);
+
+        left.set_pixel(0, 0, Color::new(1.0, 0.5, 0.25));
+        right.set_pixel(1, 0, Color::new(0.25, 0.5, 1.0));
+        right.set_coverage(0, 0, 0.5);
+
+        let side_by_side = StereoLayout::SideBySide.compose(&left, &right);
+
+        assert_eq!((4, 1), (side_by_side.width(), side_by_side.height()));
+        Color::new(1.0, 0.5, 0.25).assert_approx_eq(side_by_side.get_pixel(0, 0));
+        Color::new(0.25, 0.5, 1.0).assert_approx_eq(side_by_side.get_pixel(3, 0));
+        assert_float_absolute_eq!(0.5, side_by_side.get_coverage(2, 0));
+
+        let over_under = StereoLayout::OverUnder.compose(&left, &right);
+
+        assert_eq!((2, 2), (over_under.width(), over_under.height()));
+        Color::new(0.25, 0.5, 1.0).assert_approx_eq(over_under.get_pixel(1, 1));
+
+        let anaglyph = StereoLayout::Anaglyph.compose(&left, &right);
+
+        assert_eq!((2, 1), (anaglyph.width(), anaglyph.height()));
+        Color::new(1.0, 0.0, 0.0).assert_approx_eq(anaglyph.get_pixel(0, 0));
+        Color::new(0.0, 0.5, 1.0).assert_approx_eq(anaglyph.get_pixel(1, 0));
+    }
+}
@@ -1,5 +1,12 @@
 use crate::matrix::Matrix;
+use crate::quaternion::Quaternion;
+use crate::vector::{Point, Vector};
 
+/// How nearly parallel the axes of two rotations must be for their axis to be blended in a
+/// straight line rather than along an arc.
+const PARALLEL_AXIS_EPSILON: f64 = 1e-12;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Transformation {
     Translate(f64, f64, f64),
     Scale(f64, f64, f64),
@@ -7,6 +14,21 @@ pub enum Transformation {
     RotateY(f64),
     RotateZ(f64),
     Shear(f64, f64, f64, f64, f64, f64),
+    /// Rotates `angle` radians about `axis`, counterclockwise when looking back down the axis
+    /// toward the origin.
+    Rotate {
+        axis: Vector,
+        angle: f64,
+    },
+    /// Rotates by a unit quaternion.
+    Quaternion(Quaternion),
+    /// Places an object at `from` with its -z axis pointing toward `to` and its y axis as close
+    /// to `up` as possible, the way a camera looks along its -z axis.
+    LookAt {
+        from: Point,
+        to: Point,
+        up: Vector,
+    },
 }
 
 impl From<&Transformation> for Matrix<4> {
@@ -65,10 +87,136 @@ impl From<&Transformation> for Matrix<4> {
                 [*z_x, *z_y, 1.0, 0.0],
                 [0.0, 0.0, 0.0, 1.0],
             ]),
+
+            Transformation::Rotate { axis, angle } => {
+                Matrix::from(&Quaternion::from_axis_angle(axis, *angle))
+            }
+
+            Transformation::Quaternion(quaternion) => Matrix::from(&quaternion.normalize()),
+
+            Transformation::LookAt { from, to, up } => {
+                let forward = (to - from).normalize();
+                let right = forward.cross(up).normalize();
+                let true_up = right.cross(&forward);
+                let (x, y, z) = from.coordinates();
+
+                let [rx, ry, rz, _] = right.components();
+                let [ux, uy, uz, _] = true_up.components();
+                let [fx, fy, fz, _] = forward.components();
+
+                Matrix::new([
+                    [rx, ux, -fx, x],
+                    [ry, uy, -fy, y],
+                    [rz, uz, -fz, z],
+                    [0.0, 0.0, 0.0, 1.0],
+                ])
+            }
         }
     }
 }
 
+impl Transformation {
+    /// Returns the transformation a fraction `t` of the way from this transformation to `other`,
+    /// or `None` if they're different kinds of transformation. Translations move in a straight
+    /// line and rotations turn at a constant rate through the full difference between their
+    /// angles, so turning from 0 to 2π spins once. Rotations about arbitrary axes follow the same
+    /// rule while their axis swings along the shorter arc between the two, quaternions take the
+    /// shortest path between orientations, and look-at placements move their positions and up
+    /// vectors in straight lines.
+    pub fn interpolate(&self, other: &Transformation, t: f64) -> Option<Transformation> {
+        let lerp = |a: f64, b: f64| a + ((b - a) * t);
+        let lerp_point = |a: &Point, b: &Point| *a + &((b - a) * t);
+
+        Some(match (self, other) {
+            (Transformation::Translate(x0, y0, z0), Transformation::Translate(x1, y1, z1)) => {
+                Transformation::Translate(lerp(*x0, *x1), lerp(*y0, *y1), lerp(*z0, *z1))
+            }
+            (Transformation::Scale(x0, y0, z0), Transformation::Scale(x1, y1, z1)) => {
+                Transformation::Scale(lerp(*x0, *x1), lerp(*y0, *y1), lerp(*z0, *z1))
+            }
+            (Transformation::RotateX(a), Transformation::RotateX(b)) => {
+                Transformation::RotateX(lerp(*a, *b))
+            }
+            (Transformation::RotateY(a), Transformation::RotateY(b)) => {
+                Transformation::RotateY(lerp(*a, *b))
+            }
+            (Transformation::RotateZ(a), Transformation::RotateZ(b)) => {
+                Transformation::RotateZ(lerp(*a, *b))
+            }
+            (
+                Transformation::Shear(a0, b0, c0, d0, e0, f0),
+                Transformation::Shear(a1, b1, c1, d1, e1, f1),
+            ) => Transformation::Shear(
+                lerp(*a0, *a1),
+                lerp(*b0, *b1),
+                lerp(*c0, *c1),
+                lerp(*d0, *d1),
+                lerp(*e0, *e1),
+                lerp(*f0, *f1),
+            ),
+            (
+                Transformation::Rotate {
+                    axis: axis0,
+                    angle: angle0,
+                },
+                Transformation::Rotate {
+                    axis: axis1,
+                    angle: angle1,
+                },
+            ) => {
+                let (axis0, axis1) = (axis0.normalize(), axis1.normalize());
+
+                // Turning by θ about an axis is the same as turning by -θ about its opposite, so
+                // flip the second rotation if that brings its axis closer to the first
+                let (axis1, angle1) = if axis0.dot(&axis1) < 0.0 {
+                    (-axis1, -angle1)
+                } else {
+                    (axis1, *angle1)
+                };
+
+                Transformation::Rotate {
+                    axis: slerp_axis(&axis0, &axis1, t),
+                    angle: lerp(*angle0, angle1),
+                }
+            }
+            (Transformation::Quaternion(a), Transformation::Quaternion(b)) => {
+                Transformation::Quaternion(a.normalize().slerp(&b.normalize(), t))
+            }
+            (
+                Transformation::LookAt {
+                    from: from0,
+                    to: to0,
+                    up: up0,
+                },
+                Transformation::LookAt {
+                    from: from1,
+                    to: to1,
+                    up: up1,
+                },
+            ) => Transformation::LookAt {
+                from: lerp_point(from0, from1),
+                to: lerp_point(to0, to1),
+                up: *up0 + &((*up1 - up0) * t),
+            },
+            _ => return None,
+        })
+    }
+}
+
+/// Returns the unit vector a fraction `t` of the way along the arc between two unit vectors.
+fn slerp_axis(a: &Vector, b: &Vector, t: f64) -> Vector {
+    let dot = a.dot(b).min(1.0);
+
+    if dot > 1.0 - PARALLEL_AXIS_EPSILON {
+        return ((*a * (1.0 - t)) + &(*b * t)).normalize();
+    }
+
+    let angle = dot.acos();
+    let sin = angle.sin();
+
+    (*a * (((1.0 - t) * angle).sin() / sin)) + &(*b * ((t * angle).sin() / sin))
+}
+
 pub fn transform(transformations: &[Transformation]) -> Matrix<4> {
     let mut transformation_matrix = Matrix::<4>::identity();
 
@@ -82,8 +230,11 @@ pub fn transform(transformations: &[Transformation]) -> Matrix<4> {
 #[cfg(test)]
 mod test {
     use crate::matrix::Matrix;
+    use crate::quaternion::Quaternion;
     use crate::transform::{Transformation, transform};
     use crate::vector::{Point, Vector};
+    use assert_float_eq::assert_float_absolute_eq;
+    use std::f64::consts::PI;
 
     #[test]
     fn test_translate() {
@@ -170,6 +321,146 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_rotate() {
+        let rotation = transform(&[Transformation::Rotate {
+            axis: Vector::new(1.0, 1.0, 1.0),
+            angle: 2.0 * PI / 3.0,
+        }]);
+
+        // A third of a turn about the diagonal cycles the axes
+        Point::new(0.0, 1.0, 0.0)
+            .assert_approx_eq_epsilon(&(&rotation * &Point::new(1.0, 0.0, 0.0)), 1e-12);
+
+        Vector::new(1.0, 0.0, 0.0)
+            .assert_approx_eq_epsilon(&(&rotation * &Vector::new(0.0, 0.0, 1.0)), 1e-12);
+
+        let quaternion = transform(&[Transformation::Quaternion(Quaternion::new(
+            2.0, 0.0, 0.0, 2.0,
+        ))]);
+
+        Point::new(0.0, 1.0, 0.0)
+            .assert_approx_eq_epsilon(&(quaternion * &Point::new(1.0, 0.0, 0.0)), 1e-12);
+    }
+
+    #[test]
+    fn test_look_at() {
+        let look_at = transform(&[Transformation::LookAt {
+            from: Point::new(1.0, 2.0, 3.0),
+            to: Point::new(1.0, 2.0, 10.0),
+            up: Vector::new(0.0, 2.0, 0.0),
+        }]);
+
+        Point::new(1.0, 2.0, 3.0).assert_approx_eq(&(&look_at * &Point::new(0.0, 0.0, 0.0)));
+
+        Vector::new(0.0, 0.0, 1.0)
+            .assert_approx_eq_epsilon(&(&look_at * &Vector::new(0.0, 0.0, -1.0)), 1e-12);
+
+        Vector::new(0.0, 1.0, 0.0)
+            .assert_approx_eq_epsilon(&(&look_at * &Vector::new(0.0, 1.0, 0.0)), 1e-12);
+    }
+
+    #[test]
+    fn test_interpolate_rotations() {
+        let z = Vector::new(0.0, 0.0, 1.0);
+
+        assert_eq!(
+            Some(Transformation::Rotate {
+                axis: z,
+                angle: 2.0,
+            }),
+            Transformation::Rotate {
+                axis: z,
+                angle: 0.0
+            }
+            .interpolate(
+                &Transformation::Rotate {
+                    axis: z * 2.0,
+                    angle: 4.0,
+                },
+                0.5
+            )
+        );
+
+        // Rotations about different axes swing their axis along an arc
+        let x_to_y = Transformation::Rotate {
+            axis: Vector::new(1.0, 0.0, 0.0),
+            angle: PI / 2.0,
+        }
+        .interpolate(
+            &Transformation::Rotate {
+                axis: Vector::new(0.0, 1.0, 0.0),
+                angle: PI / 2.0,
+            },
+            0.5,
+        )
+        .unwrap();
+
+        let Transformation::Rotate { axis, angle } = x_to_y else {
+            panic!("expected a rotation, got {x_to_y:?}");
+        };
+
+        Vector::new(1.0, 1.0, 0.0)
+            .normalize()
+            .assert_approx_eq_epsilon(&axis, 1e-12);
+        assert_float_absolute_eq!(PI / 2.0, angle, 1e-12);
+
+        // Multiple turns are kept whether or not the axes are parallel, so nearly identical axes
+        // animate alike
+        let spin = |axis: Vector| {
+            let Some(Transformation::Rotate { angle, .. }) = Transformation::Rotate {
+                axis: Vector::new(0.0, 1.0, 0.0),
+                angle: 0.0,
+            }
+            .interpolate(
+                &Transformation::Rotate {
+                    axis,
+                    angle: 2.0 * PI,
+                },
+                0.5,
+            ) else {
+                panic!("expected a rotation");
+            };
+
+            angle
+        };
+
+        assert_float_absolute_eq!(PI, spin(Vector::new(0.0, 1.0, 0.0)), 1e-12);
+        assert_float_absolute_eq!(PI, spin(Vector::new(1e-6, 1.0, 0.0)), 1e-12);
+
+        // An opposite axis is flipped to match, negating its angle
+        let flipped = Transformation::Rotate {
+            axis: Vector::new(0.0, 0.0, 1.0),
+            angle: 0.0,
+        }
+        .interpolate(
+            &Transformation::Rotate {
+                axis: Vector::new(0.0, 0.0, -1.0),
+                angle: PI,
+            },
+            0.5,
+        );
+
+        assert_eq!(
+            Some(Transformation::Rotate {
+                axis: Vector::new(0.0, 0.0, 1.0),
+                angle: -PI / 2.0,
+            }),
+            flipped
+        );
+
+        let look_at = |x: f64| Transformation::LookAt {
+            from: Point::new(x, 0.0, 0.0),
+            to: Point::new(x, 0.0, 1.0),
+            up: Vector::new(0.0, 1.0, 0.0),
+        };
+
+        assert_eq!(
+            Some(look_at(1.0)),
+            look_at(0.0).interpolate(&look_at(4.0), 0.25)
+        );
+    }
+
     #[test]
     fn test_chain_transform() {
         let point = Point::new(1.0, 0.0, 1.0);
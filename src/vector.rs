@@ -1,3 +1,4 @@
+use crate::simd;
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
 #[cfg(test)]
@@ -9,7 +10,7 @@ pub const ORIGIN: Point = Point {
     components: [0.0, 0.0, 0.0, 1.0],
 };
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Point {
     components: [f64; 4],
 }
@@ -96,7 +97,7 @@ impl Sub<&Point> for &Point {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Vector {
     components: [f64; 4],
 }
@@ -113,29 +114,20 @@ impl Vector {
     }
 
     pub fn magnitude(&self) -> f64 {
-        ((self.components[0] * self.components[0])
-            + (self.components[1] * self.components[1])
-            + (self.components[2] * self.components[2]))
-            .sqrt()
+        self.dot(self).sqrt()
     }
 
     pub fn normalize(&self) -> Self {
         // TODO Do we need to do something more than panic for zero-magnitude vectors?
-        self / self.magnitude()
+        Vector::from(simd::normalize(&self.components))
     }
 
     pub fn dot(&self, rhs: &Self) -> f64 {
-        (self.components[0] * rhs.components[0])
-            + (self.components[1] * rhs.components[1])
-            + (self.components[2] * rhs.components[2])
+        simd::dot(&self.components, &rhs.components)
     }
 
     pub fn cross(&self, rhs: &Self) -> Self {
-        Vector::new(
-            (self.components[1] * rhs.components[2]) - (self.components[2] * rhs.components[1]),
-            (self.components[2] * rhs.components[0]) - (self.components[0] * rhs.components[2]),
-            (self.components[0] * rhs.components[1]) - (self.components[1] * rhs.components[0]),
-        )
+        Vector::from(simd::cross(&self.components, &rhs.components))
     }
 
     pub fn reflect(&self, normal: &Vector) -> Self {
@@ -0,0 +1,73 @@
+use crate::color::Color;
+
+/// Exponential fog that fills an entire world. Light traveling a distance `d` through fog of
+/// density `σ` keeps a fraction `e^(-σd)` of its color and takes on the fog's color in place of
+/// the rest.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fog {
+    color: Color,
+    density: f64,
+}
+
+impl Fog {
+    pub fn new(color: Color, density: f64) -> Self {
+        Self { color, density }
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    pub fn density(&self) -> f64 {
+        self.density
+    }
+
+    /// Returns the fraction of light that passes through `distance` units of this fog. Fog with no
+    /// density lets all light through, even over infinite distances.
+    pub fn transmittance(&self, distance: f64) -> f64 {
+        if self.density == 0.0 {
+            return 1.0;
+        }
+
+        (-self.density * distance).exp()
+    }
+
+    /// Returns the color seen through `distance` units of this fog when looking at a surface of
+    /// the given color. Infinite distances (rays that hit nothing) see only the fog's color, unless
+    /// the fog has no density.
+    pub fn apply(&self, color: &Color, distance: f64) -> Color {
+        let transmittance = self.transmittance(distance);
+
+        (*color * transmittance) + (self.color * (1.0 - transmittance))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::volume::fog::Fog;
+    use assert_float_eq::assert_f64_near;
+
+    #[test]
+    fn test_apply() {
+        let fog = Fog::new(Color::new(0.5, 0.5, 0.5), 0.1);
+
+        assert_f64_near!(1.0, fog.transmittance(0.0));
+        assert_f64_near!((-1.0f64).exp(), fog.transmittance(10.0));
+
+        color::WHITE.assert_approx_eq(&fog.apply(&color::WHITE, 0.0));
+        Color::new(0.5, 0.5, 0.5).assert_approx_eq(&fog.apply(&color::BLACK, f64::INFINITY));
+
+        let transmittance = fog.transmittance(10.0);
+        let expected = 0.5 * (1.0 - transmittance);
+
+        Color::new(expected, expected, expected).assert_approx_eq(&fog.apply(&color::BLACK, 10.0));
+
+        // Rays that hit nothing see straight through fog with no density
+        let clear = Fog::new(Color::new(0.5, 0.5, 0.5), 0.0);
+
+        assert_f64_near!(1.0, clear.transmittance(f64::INFINITY));
+        color::WHITE.assert_approx_eq(&clear.apply(&color::WHITE, f64::INFINITY));
+    }
+}
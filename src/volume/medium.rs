@@ -0,0 +1,125 @@
+use crate::color;
+use crate::color::Color;
+use crate::random::Random;
+use crate::volume::phase::HenyeyGreenstein;
+
+/// A medium, like smoke or murky water, with the same properties throughout. Per unit distance,
+/// a fraction `absorption` of the light passing through it is absorbed and a fraction
+/// `scattering` is scattered into other directions according to its phase function. Scattered
+/// light is tinted by the medium's color.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HomogeneousMedium {
+    absorption: f64,
+    scattering: f64,
+    color: Color,
+    phase: HenyeyGreenstein,
+}
+
+impl HomogeneousMedium {
+    pub fn new(absorption: f64, scattering: f64) -> Self {
+        Self {
+            absorption,
+            scattering,
+            color: color::WHITE,
+            phase: HenyeyGreenstein::default(),
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_phase(mut self, phase: HenyeyGreenstein) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    pub fn absorption(&self) -> f64 {
+        self.absorption
+    }
+
+    pub fn scattering(&self) -> f64 {
+        self.scattering
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    pub fn phase(&self) -> &HenyeyGreenstein {
+        &self.phase
+    }
+
+    /// Returns the fraction of light removed from a beam per unit distance by absorption and
+    /// scattering combined.
+    pub fn extinction(&self) -> f64 {
+        self.absorption + self.scattering
+    }
+
+    /// Returns the fraction of the light removed from a beam that is scattered rather than
+    /// absorbed, tinted by this medium's color.
+    pub fn albedo(&self) -> Color {
+        if self.extinction() <= 0.0 {
+            return color::BLACK;
+        }
+
+        self.color * (self.scattering / self.extinction())
+    }
+
+    /// Returns the fraction of light that passes through `distance` units of this medium.
+    pub fn transmittance(&self, distance: f64) -> f64 {
+        (-self.extinction() * distance).exp()
+    }
+
+    /// Chooses how far light travels through this medium before it is absorbed or scattered,
+    /// with probability proportional to the transmittance. Returns infinity for empty media.
+    pub fn sample_distance(&self, random: &mut Random) -> f64 {
+        if self.extinction() <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        -(1.0 - random.next_f64()).ln() / self.extinction()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::random::Random;
+    use crate::volume::medium::HomogeneousMedium;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+
+    #[test]
+    fn test_coefficients() {
+        let medium = HomogeneousMedium::new(0.1, 0.3).with_color(Color::new(1.0, 0.5, 0.0));
+
+        assert_f64_near!(0.4, medium.extinction());
+        assert_float_absolute_eq!((-0.8f64).exp(), medium.transmittance(2.0), 1e-12);
+        Color::new(0.75, 0.375, 0.0).assert_approx_eq(&medium.albedo());
+
+        color::BLACK.assert_approx_eq(&HomogeneousMedium::new(0.0, 0.0).albedo());
+    }
+
+    #[test]
+    fn test_sample_distance() {
+        let medium = HomogeneousMedium::new(0.5, 1.5);
+        let mut random = Random::new(0);
+
+        // Distances are exponentially distributed with mean 1 / extinction
+        let samples = 20_000;
+        let mean = (0..samples)
+            .map(|_| medium.sample_distance(&mut random))
+            .sum::<f64>()
+            / samples as f64;
+
+        assert_float_absolute_eq!(0.5, mean, 0.01);
+
+        assert!(
+            HomogeneousMedium::new(0.0, 0.0)
+                .sample_distance(&mut random)
+                .is_infinite()
+        );
+    }
+}
@@ -0,0 +1,143 @@
+pub mod fog;
+pub mod medium;
+pub mod phase;
+
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::vector::{Point, Vector};
+use crate::volume::medium::HomogeneousMedium;
+
+/// A region of a world filled with a participating medium. The region is the inside of a closed
+/// boundary shape, which is never drawn itself.
+pub struct Volume {
+    boundary: Box<dyn Shape>,
+    medium: HomogeneousMedium,
+}
+
+impl Volume {
+    pub fn new<S: Shape + 'static>(boundary: S, medium: HomogeneousMedium) -> Self {
+        Self {
+            boundary: Box::new(boundary),
+            medium,
+        }
+    }
+
+    pub fn boundary(&self) -> &dyn Shape {
+        self.boundary.as_ref()
+    }
+
+    pub fn medium(&self) -> &HomogeneousMedium {
+        &self.medium
+    }
+
+    /// Returns the ranges of distances along `ray` between 0 and `max_distance` that lie inside
+    /// this volume, in ascending order.
+    pub fn segments(&self, ray: &Ray, max_distance: f64) -> Vec<(f64, f64)> {
+        let mut distances: Vec<f64> = self
+            .boundary
+            .intersect(ray)
+            .iter()
+            .map(|intersection| intersection.distance())
+            .collect();
+
+        distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        // Rays enter and leave a closed boundary in turn
+        distances
+            .chunks_exact(2)
+            .map(|pair| (pair[0].max(0.0), pair[1].min(max_distance)))
+            .filter(|(enter, exit)| enter < exit)
+            .collect()
+    }
+
+    /// Returns the fraction of light that passes through this volume on its way from `point`
+    /// along the unit vector `direction` for `distance` units at the given time.
+    pub fn transmittance(
+        &self,
+        point: &Point,
+        direction: &Vector,
+        distance: f64,
+        time: f64,
+    ) -> f64 {
+        let inside: f64 = self
+            .segments(&Ray::new(*point, *direction).with_time(time), distance)
+            .iter()
+            .map(|(enter, exit)| exit - enter)
+            .sum();
+
+        self.medium.transmittance(inside)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::material::Material;
+    use crate::ray::Ray;
+    use crate::shape::sphere::Sphere;
+    use crate::transform::Transformation;
+    use crate::vector::{Point, Vector};
+    use crate::volume::Volume;
+    use crate::volume::medium::HomogeneousMedium;
+    use assert_float_eq::assert_f64_near;
+
+    fn volume() -> Volume {
+        Volume::new(
+            Sphere::with_transformations(
+                &[Transformation::Scale(2.0, 2.0, 2.0)],
+                Material::default(),
+            ),
+            HomogeneousMedium::new(0.1, 0.4),
+        )
+    }
+
+    #[test]
+    fn test_segments() {
+        let volume = volume();
+        let direction = Vector::new(0.0, 0.0, 1.0);
+
+        assert_eq!(
+            vec![(3.0, 7.0)],
+            volume.segments(
+                &Ray::new(Point::new(0.0, 0.0, -5.0), direction),
+                f64::INFINITY
+            )
+        );
+
+        // Rays starting inside the volume or stopping at a surface are clipped
+        assert_eq!(
+            vec![(0.0, 2.0)],
+            volume.segments(
+                &Ray::new(Point::new(0.0, 0.0, 0.0), direction),
+                f64::INFINITY
+            )
+        );
+        assert_eq!(
+            vec![(3.0, 4.0)],
+            volume.segments(&Ray::new(Point::new(0.0, 0.0, -5.0), direction), 4.0)
+        );
+
+        assert!(
+            volume
+                .segments(
+                    &Ray::new(Point::new(0.0, 3.0, -5.0), direction),
+                    f64::INFINITY
+                )
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_transmittance() {
+        let volume = volume();
+
+        assert_f64_near!(
+            (-2.0f64).exp(),
+            volume.transmittance(
+                &Point::new(0.0, 0.0, -5.0),
+                &Vector::new(0.0, 0.0, 1.0),
+                f64::INFINITY,
+                0.0
+            )
+        );
+    }
+}
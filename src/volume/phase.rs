@@ -0,0 +1,107 @@
+use crate::random::Random;
+use crate::sampling::warp::orthonormal_basis;
+use crate::vector::Vector;
+use std::f64::consts::PI;
+
+/// The Henyey-Greenstein phase function, which describes how much of the light scattered by a
+/// medium continues in each direction. Its asymmetry parameter `g` ranges from -1 (all light
+/// scatters backward) through 0 (light scatters equally in all directions) to 1 (all light
+/// continues forward).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HenyeyGreenstein {
+    g: f64,
+}
+
+impl HenyeyGreenstein {
+    pub fn new(g: f64) -> Self {
+        Self {
+            g: g.clamp(-0.99, 0.99),
+        }
+    }
+
+    pub fn g(&self) -> f64 {
+        self.g
+    }
+
+    /// Returns the probability density (per steradian) that light traveling along `incoming`
+    /// scatters into `outgoing`. Both vectors must be unit vectors.
+    pub fn evaluate(&self, incoming: &Vector, outgoing: &Vector) -> f64 {
+        let cos = incoming.dot(outgoing);
+        let denominator = 1.0 + (self.g * self.g) - (2.0 * self.g * cos);
+
+        (1.0 - (self.g * self.g)) / (4.0 * PI * denominator * denominator.sqrt())
+    }
+
+    /// Chooses a direction into which light traveling along `incoming` scatters, with
+    /// probability density given by `evaluate`.
+    pub fn sample(&self, incoming: &Vector, random: &mut Random) -> Vector {
+        let u = random.next_f64();
+
+        let cos = if self.g.abs() < 1e-3 {
+            1.0 - (2.0 * u)
+        } else {
+            let square = (1.0 - (self.g * self.g)) / (1.0 - self.g + (2.0 * self.g * u));
+            (1.0 + (self.g * self.g) - (square * square)) / (2.0 * self.g)
+        }
+        .clamp(-1.0, 1.0);
+
+        let sin = (1.0 - (cos * cos)).sqrt();
+        let phi = 2.0 * PI * random.next_f64();
+        let (tangent, bitangent) = orthonormal_basis(incoming);
+
+        (tangent * (sin * phi.cos())) + &(bitangent * (sin * phi.sin())) + &(*incoming * cos)
+    }
+}
+
+impl Default for HenyeyGreenstein {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::random::Random;
+    use crate::vector::Vector;
+    use crate::volume::phase::HenyeyGreenstein;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_evaluate() {
+        let forward = Vector::new(0.0, 0.0, 1.0);
+        let backward = Vector::new(0.0, 0.0, -1.0);
+
+        // Isotropic scattering sends light equally in all directions
+        assert_f64_near!(
+            1.0 / (4.0 * PI),
+            HenyeyGreenstein::default().evaluate(&forward, &backward)
+        );
+
+        let phase = HenyeyGreenstein::new(0.7);
+
+        assert!(phase.evaluate(&forward, &forward) > phase.evaluate(&forward, &backward));
+    }
+
+    #[test]
+    fn test_sample() {
+        let incoming = Vector::new(0.0, 1.0, 0.0);
+        let mut random = Random::new(0);
+
+        for g in [-0.5, 0.0, 0.8] {
+            let phase = HenyeyGreenstein::new(g);
+            let samples = 20_000;
+            let mut mean_cos = 0.0;
+
+            for _ in 0..samples {
+                let direction = phase.sample(&incoming, &mut random);
+
+                assert_float_absolute_eq!(1.0, direction.magnitude(), 1e-9);
+                mean_cos += direction.dot(&incoming);
+            }
+
+            // The mean cosine of the scattering angle is g
+            assert_float_absolute_eq!(g, mean_cos / samples as f64, 0.02);
+        }
+    }
+}
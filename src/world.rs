@@ -0,0 +1,935 @@
+use crate::color;
+use crate::color::Color;
+use crate::intersection;
+use crate::intersection::Intersection;
+use crate::light::SceneLight;
+use crate::random::Random;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::shape::mesh::Mesh;
+use crate::vector::{Point, Vector};
+use crate::volume::Volume;
+use crate::volume::fog::Fog;
+use crate::volume::medium::HomogeneousMedium;
+use std::f64::consts::PI;
+
+/// How far to nudge shadow ray origins off of surfaces to keep them from shadowing themselves.
+pub const SHADOW_EPSILON: f64 = 1e-6;
+
+/// A collection of shapes that rays can be traced against, the lights that illuminate them and
+/// the fog and volumes that light passes through.
+pub struct World {
+    shapes: Vec<Box<dyn Shape>>,
+    lights: Vec<SceneLight>,
+    ambient: Color,
+    emitters: Vec<Emitter>,
+    emitter_samples: usize,
+    fog: Option<Fog>,
+    volumes: Vec<Volume>,
+    volume_samples: usize,
+    subsurface_samples: usize,
+}
+
+/// An emissive shape, identified by its index, and the total area of it and all emissive shapes
+/// added before it.
+struct Emitter {
+    shape: usize,
+    cumulative_area: f64,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self {
+            shapes: Vec::new(),
+            lights: Vec::new(),
+            ambient: color::WHITE,
+            emitters: Vec::new(),
+            emitter_samples: 16,
+            fog: None,
+            volumes: Vec::new(),
+            volume_samples: 32,
+            subsurface_samples: 16,
+        }
+    }
+
+    /// Adds a shape to this world. Shapes with emissive materials also light the world.
+    pub fn add_shape<S: Shape + 'static>(&mut self, shape: S) {
+        if shape.material().is_emissive() {
+            self.emitters.push(Emitter {
+                shape: self.shapes.len(),
+                cumulative_area: self.emissive_area() + shape.area(),
+            });
+        }
+
+        self.shapes.push(Box::new(shape));
+    }
+
+    /// Adds each of a mesh's triangles to this world.
+    pub fn add_mesh(&mut self, mesh: Mesh) {
+        for triangle in mesh.into_triangles() {
+            self.add_shape(triangle);
+        }
+    }
+
+    pub fn shapes(&self) -> &[Box<dyn Shape>] {
+        &self.shapes
+    }
+
+    pub fn add_light(&mut self, light: SceneLight) {
+        self.lights.push(light);
+    }
+
+    pub fn lights(&self) -> &[SceneLight] {
+        &self.lights
+    }
+
+    pub fn lights_mut(&mut self) -> &mut [SceneLight] {
+        &mut self.lights
+    }
+
+    /// Returns the intensity of the ambient light that reaches every surface in this world,
+    /// which materials scale by their ambient coefficient.
+    pub fn ambient(&self) -> &Color {
+        &self.ambient
+    }
+
+    pub fn set_ambient(&mut self, ambient: Color) {
+        self.ambient = ambient;
+    }
+
+    pub fn fog(&self) -> Option<&Fog> {
+        self.fog.as_ref()
+    }
+
+    pub fn set_fog(&mut self, fog: Option<Fog>) {
+        self.fog = fog;
+    }
+
+    pub fn add_volume(&mut self, volume: Volume) {
+        self.volumes.push(volume);
+    }
+
+    pub fn volumes(&self) -> &[Volume] {
+        &self.volumes
+    }
+
+    /// Returns the number of points along each segment of a ray inside a volume at which
+    /// `color_at` gathers scattered light.
+    pub fn volume_samples(&self) -> usize {
+        self.volume_samples
+    }
+
+    pub fn set_volume_samples(&mut self, volume_samples: usize) {
+        self.volume_samples = volume_samples;
+    }
+
+    /// Returns the number of random walks that `shade` follows beneath the surface of each
+    /// shaded point on a translucent material.
+    pub fn subsurface_samples(&self) -> usize {
+        self.subsurface_samples
+    }
+
+    pub fn set_subsurface_samples(&mut self, subsurface_samples: usize) {
+        self.subsurface_samples = subsurface_samples;
+    }
+
+    /// Returns the total surface area of this world's emissive shapes.
+    pub fn emissive_area(&self) -> f64 {
+        self.emitters
+            .last()
+            .map_or(0.0, |emitter| emitter.cumulative_area)
+    }
+
+    /// Returns the number of points on emissive shapes that `shade` samples for each shaded
+    /// point.
+    pub fn emitter_samples(&self) -> usize {
+        self.emitter_samples
+    }
+
+    pub fn set_emitter_samples(&mut self, emitter_samples: usize) {
+        self.emitter_samples = emitter_samples;
+    }
+
+    /// Chooses a point on this world's emissive shapes with probability proportional to area and
+    /// returns the direction from `position` toward it along with the irradiance it contributes at
+    /// `position` at the given time, as if it were the only sample. Emissive surfaces emit light
+    /// from both sides. Points on surfaces pass their normal, and points inside volumes pass
+    /// `None`. Returns `None` if there are no emissive shapes or if the chosen point isn't visible
+    /// from `position`.
+    pub fn sample_emitter(
+        &self,
+        position: &Point,
+        normal: Option<&Vector>,
+        time: f64,
+        random: &mut Random,
+    ) -> Option<(Vector, Color)> {
+        let total_area = self.emissive_area();
+
+        if total_area <= 0.0 {
+            return None;
+        }
+
+        let target = random.next_f64() * total_area;
+        let index = self
+            .emitters
+            .partition_point(|emitter| emitter.cumulative_area <= target)
+            .min(self.emitters.len() - 1);
+
+        let shape = self.shapes[self.emitters[index].shape].as_ref();
+        let (point, emitter_normal, pdf) = shape.sample_surface(time, random);
+
+        let to_emitter = point - position;
+        let distance_squared = to_emitter.dot(&to_emitter);
+
+        if distance_squared < SHADOW_EPSILON * SHADOW_EPSILON {
+            return None;
+        }
+
+        let distance = distance_squared.sqrt();
+        let direction = to_emitter / distance;
+
+        let over_position = match normal {
+            Some(normal) if direction.dot(normal) <= 0.0 => return None,
+            Some(normal) => *position + &(*normal * SHADOW_EPSILON),
+            None => *position,
+        };
+
+        if self.is_occluded(
+            &over_position,
+            &direction,
+            distance * (1.0 - SHADOW_EPSILON),
+            time,
+        ) {
+            return None;
+        }
+
+        // The shape was chosen in proportion to its area, and the point on it with density `pdf`.
+        // Convert the combined density from per unit area to per unit solid angle.
+        let pdf = pdf * shape.area() / total_area;
+        let cos_emitter = direction.dot(&emitter_normal).abs();
+        let transmittance = self.transmittance(&over_position, &direction, distance, time);
+
+        Some((
+            direction,
+            *shape.material().emission() * (cos_emitter * transmittance / (distance_squared * pdf)),
+        ))
+    }
+
+    /// Returns the fraction of a light that reaches `point` at the given time, accounting for
+    /// both shadows (if the light casts them) and volumes along the way. Points on surfaces
+    /// should be offset slightly away from the surface.
+    pub fn visibility(
+        &self,
+        light: &SceneLight,
+        point: &Point,
+        time: f64,
+        random: &mut Random,
+    ) -> f64 {
+        let visibility = if light.casts_shadows() {
+            light.light().visibility(self, point, time, random)
+        } else {
+            1.0
+        };
+
+        visibility
+            * self.transmittance(
+                point,
+                &light.light().direction_from(point),
+                light.light().distance_from(point),
+                time,
+            )
+    }
+
+    /// Returns the light arriving at a point on a surface with the given normal at the given
+    /// time from this world's lights and one sample of its emissive shapes, weighted by the
+    /// cosine of its angle with the normal.
+    pub fn irradiance(
+        &self,
+        position: &Point,
+        normal: &Vector,
+        time: f64,
+        random: &mut Random,
+    ) -> Color {
+        let over_position = *position + &(*normal * SHADOW_EPSILON);
+
+        let emitted = match self.sample_emitter(position, Some(normal), time, random) {
+            Some((direction, irradiance)) => irradiance * direction.dot(normal),
+            None => color::BLACK,
+        };
+
+        self.lights
+            .iter()
+            .filter(|light| light.is_enabled())
+            .fold(emitted, |color, light| {
+                let cos = light.light().direction_from(position).dot(normal);
+
+                if cos <= 0.0 {
+                    return color;
+                }
+
+                color
+                    + (light.light().intensity_at(position)
+                        * (cos * self.visibility(light, &over_position, time, random)))
+            })
+    }
+
+    /// Returns the fraction of light that passes through this world's volumes on its way from
+    /// `point` along the unit vector `direction` for `distance` units at the given time. Fog
+    /// doesn't affect the result.
+    pub fn transmittance(
+        &self,
+        point: &Point,
+        direction: &Vector,
+        distance: f64,
+        time: f64,
+    ) -> f64 {
+        self.volumes
+            .iter()
+            .map(|volume| volume.transmittance(point, direction, distance, time))
+            .product()
+    }
+
+    /// Returns the light arriving at `point` inside a medium at the given time from this world's
+    /// lights and one sample of its emissive shapes that scatters back along the unit vector
+    /// `direction`, before accounting for the medium's scattering coefficient and color.
+    pub fn in_scattered(
+        &self,
+        medium: &HomogeneousMedium,
+        point: &Point,
+        direction: &Vector,
+        time: f64,
+        random: &mut Random,
+    ) -> Color {
+        let toward_eye = -*direction;
+
+        let emitted = match self.sample_emitter(point, None, time, random) {
+            Some((light_vector, irradiance)) => {
+                irradiance * medium.phase().evaluate(&-light_vector, &toward_eye)
+            }
+            None => color::BLACK,
+        };
+
+        self.lights
+            .iter()
+            .filter(|light| light.is_enabled())
+            .fold(emitted, |color, light| {
+                let light_vector = light.light().direction_from(point);
+                let phase = medium.phase().evaluate(&-light_vector, &toward_eye);
+
+                color
+                    + (light.light().intensity_at(point)
+                        * (phase * self.visibility(light, point, time, random)))
+            })
+    }
+
+    /// Chooses where, if anywhere, light traveling along `ray` is first absorbed or scattered
+    /// by one of this world's volumes before traveling `max_distance`. Returns the distance
+    /// along the ray and the volume responsible.
+    pub fn sample_volume_interaction(
+        &self,
+        ray: &Ray,
+        max_distance: f64,
+        random: &mut Random,
+    ) -> Option<(f64, &Volume)> {
+        self.volumes
+            .iter()
+            .filter_map(|volume| {
+                volume
+                    .segments(ray, max_distance)
+                    .into_iter()
+                    .find_map(|(enter, exit)| {
+                        let distance = enter + volume.medium().sample_distance(random);
+
+                        (distance < exit).then_some((distance, volume))
+                    })
+            })
+            .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
+    }
+
+    /// Returns the color seen along the given ray or `None` if the ray doesn't hit anything and
+    /// passes through no fog or volumes. Light scattered toward the eye by volumes is gathered
+    /// at `volume_samples` points along each segment inside a volume.
+    pub fn color_at(&self, ray: &Ray, random: &mut Random) -> Option<Color> {
+        let intersections = self.intersect(ray);
+        let hit = intersection::hit(&intersections);
+        let distance = hit.map_or(f64::INFINITY, |hit| hit.distance());
+
+        // Gather volumes from back to front, so that nearer volumes attenuate farther ones
+        let mut segments: Vec<(&Volume, f64, f64)> = self
+            .volumes
+            .iter()
+            .flat_map(|volume| {
+                volume
+                    .segments(ray, distance)
+                    .into_iter()
+                    .map(move |(enter, exit)| (volume, enter, exit))
+            })
+            .collect();
+
+        if hit.is_none() && segments.is_empty() && self.fog.is_none() {
+            return None;
+        }
+
+        segments.sort_by(|(_, a, _), (_, b, _)| b.partial_cmp(a).unwrap());
+
+        let mut color = match hit {
+            Some(hit) => {
+                let shape = hit.shape();
+                let position = ray.position(hit.distance());
+
+                self.shade(
+                    shape,
+                    &position,
+                    &-*ray.direction(),
+                    &shape.normal_at(&position, ray.time()),
+                    ray.time(),
+                    random,
+                )
+            }
+            None => color::BLACK,
+        };
+
+        for (volume, enter, exit) in segments {
+            color = (color * volume.medium().transmittance(exit - enter))
+                + self.scatter(volume.medium(), ray, enter, exit, random);
+        }
+
+        Some(match &self.fog {
+            Some(fog) => fog.apply(&color, distance),
+            None => color,
+        })
+    }
+
+    /// Returns the light scattered toward the origin of `ray` by the part of a medium between
+    /// `enter` and `exit`, scaled by π like the rest of the Phong model's results.
+    fn scatter(
+        &self,
+        medium: &HomogeneousMedium,
+        ray: &Ray,
+        enter: f64,
+        exit: f64,
+        random: &mut Random,
+    ) -> Color {
+        let samples = self.volume_samples.max(1);
+        let step = (exit - enter) / samples as f64;
+
+        let in_scattered = (0..samples).fold(color::BLACK, |color, i| {
+            let distance = enter + (step * (i as f64 + random.next_f64()));
+            let point = ray.position(distance);
+
+            color
+                + (self.in_scattered(medium, &point, ray.direction(), ray.time(), random)
+                    * medium.transmittance(distance - enter))
+        });
+
+        in_scattered * *medium.color() * (medium.scattering() * step * PI)
+    }
+
+    /// Computes the color of a point on a shape's surface at the given time. The world's ambient
+    /// light and the material's emission are counted once, the diffuse and specular contributions
+    /// of each enabled light are summed, and light from emissive shapes is averaged over
+    /// `emitter_samples` points. Light scattered beneath the surfaces of translucent materials is
+    /// averaged over `subsurface_samples` random walks, each of which gathers the irradiance where
+    /// it leaves the shape. Like the rest of the Phong model's results, emission is scaled by π
+    /// relative to the path integrator's.
+    pub fn shade(
+        &self,
+        shape: &dyn Shape,
+        position: &Point,
+        eye: &Vector,
+        normal: &Vector,
+        time: f64,
+        random: &mut Random,
+    ) -> Color {
+        let material = shape.material();
+        let over_position = *position + &(*normal * SHADOW_EPSILON);
+
+        let mut color = material.ambient_lighting(&self.ambient) + (*material.emission() * PI);
+
+        if self.emissive_area() > 0.0 && self.emitter_samples > 0 {
+            let samples = self.emitter_samples as f64;
+
+            for _ in 0..self.emitter_samples {
+                if let Some((direction, irradiance)) =
+                    self.sample_emitter(position, Some(normal), time, random)
+                {
+                    color = color
+                        + material.reflected(
+                            &direction,
+                            &(irradiance * (1.0 / samples)),
+                            eye,
+                            normal,
+                        );
+                }
+            }
+        }
+
+        if let Some(subsurface) = material.subsurface()
+            && self.subsurface_samples > 0
+        {
+            let samples = self.subsurface_samples as f64;
+
+            for _ in 0..self.subsurface_samples {
+                if let Some((exit, exit_normal, weight)) =
+                    subsurface.walk(shape, position, normal, time, random)
+                {
+                    color = color
+                        + (self.irradiance(&exit, &exit_normal, time, random)
+                            * weight
+                            * (1.0 / samples));
+                }
+            }
+        }
+
+        self.lights
+            .iter()
+            .filter(|light| light.is_enabled())
+            .fold(color, |color, light| {
+                let visibility = self.visibility(light, &over_position, time, random);
+
+                color + material.direct_lighting(light.light(), position, eye, normal, visibility)
+            })
+    }
+
+    /// Returns every intersection of the given ray with the shapes in this world in ascending
+    /// order of distance.
+    pub fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections: Vec<Intersection> = self
+            .shapes
+            .iter()
+            .flat_map(|shape| shape.intersect(ray))
+            .collect();
+
+        intersections.sort_by(|a, b| a.distance().partial_cmp(&b.distance()).unwrap());
+        intersections
+    }
+
+    /// Returns `true` if any shape lies between `point` and `light_position` at the given time.
+    /// Callers should offset `point` slightly away from the surface it lies on to avoid shadowing
+    /// it by itself.
+    pub fn is_shadowed(&self, point: &Point, light_position: &Point, time: f64) -> bool {
+        let point_to_light = light_position - point;
+
+        self.is_occluded(
+            point,
+            &point_to_light.normalize(),
+            point_to_light.magnitude(),
+            time,
+        )
+    }
+
+    /// Returns `true` if any shape lies within `distance` of `point` along the unit vector
+    /// `direction` at the given time.
+    pub fn is_occluded(&self, point: &Point, direction: &Vector, distance: f64, time: f64) -> bool {
+        let intersections = self.intersect(&Ray::new(*point, *direction).with_time(time));
+
+        intersection::hit(&intersections).is_some_and(|hit| hit.distance() < distance)
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::color;
+    use crate::color::Color;
+    use crate::light::SceneLight;
+    use crate::light::point::PointLight;
+    use crate::material::Material;
+    use crate::material::subsurface::Subsurface;
+    use crate::random::Random;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::shape::mesh::Mesh;
+    use crate::shape::quad::Quad;
+    use crate::shape::sphere::Sphere;
+    use crate::transform::Transformation;
+    use crate::vector::{ORIGIN, Point, Vector};
+    use crate::volume::Volume;
+    use crate::volume::fog::Fog;
+    use crate::volume::medium::HomogeneousMedium;
+    use crate::world::World;
+    use assert_float_eq::{assert_f64_near, assert_float_absolute_eq};
+
+    fn world() -> World {
+        let mut world = World::new();
+
+        world.add_shape(Sphere::default());
+        world.add_shape(Sphere::with_transformations(
+            &[Transformation::Scale(0.5, 0.5, 0.5)],
+            Material::default(),
+        ));
+
+        world
+    }
+
+    #[test]
+    fn test_intersect() {
+        let world = world();
+        let intersections = world.intersect(&Ray::new(
+            Point::new(0.0, 0.0, -5.0),
+            Vector::new(0.0, 0.0, 1.0),
+        ));
+
+        let distances: Vec<f64> = intersections.iter().map(|i| i.distance()).collect();
+
+        assert_eq!(vec![4.0, 4.5, 5.5, 6.0], distances);
+    }
+
+    #[test]
+    fn test_is_shadowed() {
+        let world = world();
+        let light = Point::new(-10.0, 10.0, -10.0);
+
+        // Nothing between the point and the light
+        assert!(!world.is_shadowed(&Point::new(0.0, 10.0, 0.0), &light, 0.0));
+
+        // The spheres lie between the point and the light
+        assert!(world.is_shadowed(&Point::new(10.0, -10.0, 10.0), &light, 0.0));
+
+        // The light lies between the point and the spheres
+        assert!(!world.is_shadowed(&Point::new(-20.0, 20.0, -20.0), &light, 0.0));
+
+        // The point lies between the light and the spheres
+        assert!(!world.is_shadowed(&Point::new(-2.0, 2.0, -2.0), &light, 0.0));
+    }
+
+    #[test]
+    fn test_shade_multiple_lights() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut random = Random::new(0);
+
+        // With no lights, only ambient light remains
+        Color::new(0.1, 0.1, 0.1).assert_approx_eq(&world.color_at(&ray, &mut random).unwrap());
+
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            color::WHITE,
+        )));
+
+        Color::new(1.9, 1.9, 1.9).assert_approx_eq(&world.color_at(&ray, &mut random).unwrap());
+
+        // A second light adds its diffuse and specular contributions, but ambient isn't repeated
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            Color::new(0.5, 0.5, 0.5),
+        )));
+
+        Color::new(2.8, 2.8, 2.8).assert_approx_eq(&world.color_at(&ray, &mut random).unwrap());
+
+        world.lights_mut()[0].set_enabled(false);
+
+        Color::new(1.0, 1.0, 1.0).assert_approx_eq(&world.color_at(&ray, &mut random).unwrap());
+
+        assert!(
+            world
+                .color_at(
+                    &Ray::new(Point::new(0.0, 5.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+                    &mut random
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_shade_shadow_flags() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+        world.add_shape(Sphere::with_transformations(
+            &[Transformation::Translate(0.0, 0.0, -3.0)],
+            Material::default(),
+        ));
+
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            color::WHITE,
+        )));
+
+        // The second sphere lies between the light and the front of the first
+        let position = Point::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let mut random = Random::new(0);
+
+        let shade = |world: &World, random: &mut Random| {
+            world.shade(
+                world.shapes()[0].as_ref(),
+                &position,
+                &normal,
+                &normal,
+                0.0,
+                random,
+            )
+        };
+
+        Color::new(0.1, 0.1, 0.1).assert_approx_eq(&shade(&world, &mut random));
+
+        world.lights_mut()[0].set_casts_shadows(false);
+
+        Color::new(1.9, 1.9, 1.9).assert_approx_eq(&shade(&world, &mut random));
+    }
+
+    #[test]
+    fn test_sample_emitter() {
+        let mut world = World::new();
+        let mut random = Random::new(0);
+        let position = Point::new(0.0, 0.0, 0.0);
+        let normal = Vector::new(0.0, 1.0, 0.0);
+
+        assert!(
+            world
+                .sample_emitter(&position, Some(&normal), 0.0, &mut random)
+                .is_none()
+        );
+
+        // Non-emissive shapes aren't sampled
+        world.add_shape(Sphere::with_transformations(
+            &[Transformation::Translate(0.0, -5.0, 0.0)],
+            Material::default(),
+        ));
+
+        // A 1×1 panel to the left and a 3×1 panel to the right
+        let emissive = Material::default().with_emission(color::WHITE);
+
+        world.add_shape(Quad::new(
+            Point::new(-2.0, 5.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+            emissive.clone(),
+        ));
+        world.add_shape(Quad::new(
+            Point::new(1.0, 5.0, 0.0),
+            Vector::new(3.0, 0.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0),
+            emissive,
+        ));
+
+        assert_float_absolute_eq!(4.0, world.emissive_area());
+
+        let samples = 10_000;
+        let right = (0..samples)
+            .filter(|_| {
+                let (direction, _) = world
+                    .sample_emitter(&position, Some(&normal), 0.0, &mut random)
+                    .unwrap();
+
+                direction.components()[0] > 0.0
+            })
+            .count();
+
+        assert_float_absolute_eq!(0.75, right as f64 / samples as f64, 0.02);
+
+        // Points facing away from every emitter receive nothing
+        assert!(
+            world
+                .sample_emitter(&position, Some(&-normal), 0.0, &mut random)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_shade_emitter() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut random = Random::new(0);
+
+        // A tiny panel with the power of a unit point light, 9 units from the hit point
+        let side = 0.01;
+        let panel = Quad::new(
+            Point::new(-side / 2.0, -side / 2.0, -10.0),
+            Vector::new(side, 0.0, 0.0),
+            Vector::new(0.0, side, 0.0),
+            Material::default().with_emission(color::WHITE * (81.0 / (side * side))),
+        );
+
+        assert!(panel.material().is_emissive());
+        world.add_shape(panel);
+
+        Color::new(1.9, 1.9, 1.9)
+            .assert_approx_eq_epsilon(&world.color_at(&ray, &mut random).unwrap(), 1e-3);
+
+        // Emissive shapes can be added as meshes, too
+        let mut world = World::new();
+        world.add_mesh(Mesh::new(
+            &[
+                Point::new(0.0, 0.0, 0.0),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(0.0, 1.0, 0.0),
+            ],
+            &[[0, 1, 2], [0, 2, 1]],
+            Material::default().with_emission(color::WHITE),
+        ));
+
+        assert_eq!(2, world.shapes().len());
+        assert_float_absolute_eq!(1.0, world.emissive_area());
+    }
+
+    #[test]
+    fn test_color_at_fog() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            color::WHITE,
+        )));
+
+        let fog = Fog::new(Color::new(0.5, 0.5, 0.5), 0.1);
+        world.set_fog(Some(fog));
+
+        let mut random = Random::new(0);
+
+        // The sphere is 4 units away
+        fog.apply(&Color::new(1.9, 1.9, 1.9), 4.0).assert_approx_eq(
+            &world
+                .color_at(
+                    &Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+                    &mut random,
+                )
+                .unwrap(),
+        );
+
+        // Rays that miss everything see only fog
+        let miss = Ray::new(Point::new(0.0, 5.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        Color::new(0.5, 0.5, 0.5).assert_approx_eq(&world.color_at(&miss, &mut random).unwrap());
+
+        world.set_fog(None);
+
+        assert!(world.color_at(&miss, &mut random).is_none());
+    }
+
+    #[test]
+    fn test_color_at_volume() {
+        let mut world = World::new();
+        world.add_shape(Sphere::default());
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            color::WHITE,
+        )));
+
+        // A purely absorbing ball of diameter 1 between the eye and the sphere
+        world.add_volume(Volume::new(
+            Sphere::with_transformations(
+                &[
+                    Transformation::Scale(0.5, 0.5, 0.5),
+                    Transformation::Translate(0.0, 0.0, -3.0),
+                ],
+                Material::default(),
+            ),
+            HomogeneousMedium::new(0.5, 0.0),
+        ));
+
+        let mut random = Random::new(0);
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let transmittance = (-0.5f64).exp();
+
+        // The volume's boundary isn't a surface, so it casts no shadow, but the volume absorbs
+        // light on its way from the light to the sphere (though not ambient light) and again on
+        // its way to the eye
+        let expected = (0.1 + (1.8 * transmittance)) * transmittance;
+
+        Color::new(expected, expected, expected)
+            .assert_approx_eq(&world.color_at(&ray, &mut random).unwrap());
+
+        // Scattering volumes are visible even where no surface lies behind them
+        let mut world = World::new();
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 10.0, 0.0),
+            color::WHITE,
+        )));
+        world.add_volume(Volume::new(
+            Sphere::default(),
+            HomogeneousMedium::new(0.0, 0.5),
+        ));
+
+        let color = world.color_at(&ray, &mut random).unwrap();
+
+        assert!(color.luminance() > 0.0);
+        assert!(
+            world
+                .color_at(
+                    &Ray::new(Point::new(0.0, 5.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+                    &mut random
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_visibility_volume() {
+        let mut world = World::new();
+
+        // An opaque sphere and a purely absorbing ball of diameter 1 between the origin and a
+        // light
+        world.add_shape(Sphere::with_transformations(
+            &[
+                Transformation::Scale(0.5, 0.5, 0.5),
+                Transformation::Translate(0.0, 0.0, -6.0),
+            ],
+            Material::default(),
+        ));
+        world.add_volume(Volume::new(
+            Sphere::with_transformations(
+                &[
+                    Transformation::Scale(0.5, 0.5, 0.5),
+                    Transformation::Translate(0.0, 0.0, -3.0),
+                ],
+                Material::default(),
+            ),
+            HomogeneousMedium::new(0.5, 0.0),
+        ));
+
+        let light = SceneLight::new(PointLight::new(Point::new(0.0, 0.0, -10.0), color::WHITE));
+        let mut random = Random::new(0);
+
+        assert_f64_near!(0.0, world.visibility(&light, &ORIGIN, 0.0, &mut random));
+
+        // Lights that cast no shadows ignore the sphere but are still absorbed by the volume
+        let light = light.with_shadows(false);
+
+        assert_float_absolute_eq!(
+            (-0.5f64).exp(),
+            world.visibility(&light, &ORIGIN, 0.0, &mut random),
+            1e-9
+        );
+    }
+
+    #[test]
+    fn test_shade_subsurface() {
+        let material = Material::new(color::WHITE, 0.1, 0.0, 0.0, 200.0);
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut random = Random::new(0);
+
+        // A light behind a sphere leaves an opaque sphere's front with only ambient light...
+        let mut world = World::new();
+        world.add_shape(Sphere::with_transformations(&[], material.clone()));
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, 10.0),
+            color::WHITE,
+        )));
+
+        Color::new(0.1, 0.1, 0.1).assert_approx_eq(&world.color_at(&ray, &mut random).unwrap());
+
+        // ...but shines through a translucent one
+        let mut world = World::new();
+        world.add_shape(Sphere::with_transformations(
+            &[],
+            material.with_subsurface(Subsurface::new(color::WHITE, Color::new(0.5, 0.5, 0.5))),
+        ));
+        world.add_light(SceneLight::new(PointLight::new(
+            Point::new(0.0, 0.0, 10.0),
+            color::WHITE,
+        )));
+        world.set_subsurface_samples(256);
+
+        assert!(world.color_at(&ray, &mut random).unwrap().luminance() > 0.12);
+    }
+}